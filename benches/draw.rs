@@ -0,0 +1,68 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use stareditor::model::{GameObject, Scene};
+
+// 씬 뷰 한 프레임에서 오브젝트마다 하는 계산 (월드 변환 → 꼭짓점 → 경계)
+const OBJECTS: usize = 5000;
+
+fn scene() -> Scene {
+    let mut scene = Scene::default();
+    for id in 0..OBJECTS {
+        scene.objects.push(GameObject {
+            id,
+            name: format!("Object {}", id),
+            position: [(id % 100) as f32 * 3.0, (id / 100) as f32 * 3.0],
+            rotation: id as f32 * 0.01,
+            scale: [1.0, 1.0],
+            image_path: None,
+            tint: [255; 4],
+            tags: Vec::new(),
+            properties: Default::default(),
+            uv_rect: None,
+            layer: None,
+            // 열 개마다 하나는 앞 오브젝트의 자식
+            parent: (id % 10 != 0).then(|| id - 1),
+            pivot: [0.5, 0.5],
+            note: String::new(),
+            flip_x: false,
+            flip_y: false,
+            snap: true,
+            anchor_to: None,
+            anchor_offset: [0.0, 0.0],
+        });
+    }
+    scene
+}
+
+fn draw(c: &mut Criterion) {
+    let scene = scene();
+    let worlds = scene.world_transforms();
+    let mut group = c.benchmark_group("draw_5k");
+    group.bench_function("world_transforms", |b| b.iter(|| black_box(scene.world_transforms())));
+    // 예전 방식: 오브젝트마다 꼭짓점 Vec을 새로 만듦
+    group.bench_function("quads_vec_per_object", |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for (obj, world) in scene.objects.iter().zip(&worlds) {
+                let quad: Vec<[f32; 2]> = world.centered(obj.pivot).corners().to_vec();
+                sum += black_box(quad)[0][0];
+            }
+            sum
+        })
+    });
+    // 지금 방식: 꼭짓점은 스택의 배열
+    group.bench_function("quads_array", |b| {
+        b.iter(|| {
+            let mut sum = 0.0;
+            for (obj, world) in scene.objects.iter().zip(&worlds) {
+                let quad = world.centered(obj.pivot).corners();
+                sum += black_box(quad)[0][0];
+            }
+            sum
+        })
+    });
+    group.bench_function("bounds", |b| b.iter(|| black_box(scene.bounds())));
+    group.finish();
+}
+
+criterion_group!(benches, draw);
+criterion_main!(benches);
@@ -0,0 +1,138 @@
+use crate::model::{GameObject, Scene};
+
+#[derive(Default)]
+pub struct SceneDiff {
+    pub added: Vec<usize>,
+    pub removed: Vec<usize>,
+    pub modified: Vec<(usize, Vec<String>)>,
+    pub moved: Vec<(usize, [f32; 2], [f32; 2])>,
+}
+
+impl SceneDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+fn changed_fields(a: &GameObject, b: &GameObject) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut check = |name: &str, changed: bool, detail: String| {
+        if changed {
+            fields.push(format!("{}: {}", name, detail));
+        }
+    };
+    check("name", a.name != b.name, format!("{:?} → {:?}", a.name, b.name));
+    check("position", a.position != b.position, format!("{:?} → {:?}", a.position, b.position));
+    check("rotation", a.rotation != b.rotation, format!("{} → {}", a.rotation, b.rotation));
+    check("scale", a.scale != b.scale, format!("{:?} → {:?}", a.scale, b.scale));
+    check("image_path", a.image_path != b.image_path, format!("{:?} → {:?}", a.image_path, b.image_path));
+    check("tint", a.tint != b.tint, format!("{:?} → {:?}", a.tint, b.tint));
+    check("tags", a.tags != b.tags, format!("{:?} → {:?}", a.tags, b.tags));
+    check("properties", a.properties != b.properties, format!("{:?} → {:?}", a.properties, b.properties));
+    check("uv_rect", a.uv_rect != b.uv_rect, format!("{:?} → {:?}", a.uv_rect, b.uv_rect));
+    check("layer", a.layer != b.layer, format!("{:?} → {:?}", a.layer, b.layer));
+    check("pivot", a.pivot != b.pivot, format!("{:?} → {:?}", a.pivot, b.pivot));
+    check("note", a.note != b.note, format!("{:?} → {:?}", a.note, b.note));
+    check("flip", (a.flip_x, a.flip_y) != (b.flip_x, b.flip_y), format!("{:?} → {:?}", (a.flip_x, a.flip_y), (b.flip_x, b.flip_y)));
+    check("snap", a.snap != b.snap, format!("{} → {}", a.snap, b.snap));
+    check("anchor", (a.anchor_to, a.anchor_offset) != (b.anchor_to, b.anchor_offset), format!("{:?} → {:?}", (a.anchor_to, a.anchor_offset), (b.anchor_to, b.anchor_offset)));
+    check("parent", a.parent != b.parent, format!("{:?} → {:?}", a.parent, b.parent));
+    fields
+}
+
+// base 기준으로 other에서 바뀐 점을 id로 비교
+pub fn diff(base: &Scene, other: &Scene) -> SceneDiff {
+    let mut result = SceneDiff::default();
+    let base_worlds = base.world_transforms();
+    let other_worlds = other.world_transforms();
+    for (i, obj) in base.objects.iter().enumerate() {
+        match other.objects.iter().position(|o| o.id == obj.id) {
+            None => result.removed.push(obj.id),
+            Some(j) => {
+                let theirs = &other.objects[j];
+                let fields = changed_fields(obj, theirs);
+                let (from, to) = (base_worlds[i].position, other_worlds[j].position);
+                if from != to {
+                    result.moved.push((obj.id, from, to));
+                }
+                if !fields.is_empty() {
+                    result.modified.push((obj.id, fields));
+                }
+            }
+        }
+    }
+    for obj in &other.objects {
+        if !base.objects.iter().any(|o| o.id == obj.id) {
+            result.added.push(obj.id);
+        }
+    }
+    result
+}
+
+#[derive(Default)]
+pub struct Compare {
+    pub open: bool,
+    pub path: String,
+    pub other: Option<Scene>,
+    pub result: SceneDiff,
+    pub error: Option<String>,
+}
+
+impl Compare {
+    pub fn ui(&mut self, ui: &mut eframe::egui::Ui, base: &Scene) {
+        use eframe::egui;
+
+        ui.horizontal(|ui| {
+            ui.label("Compare with:");
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("Compare").clicked() {
+                match crate::save::load_scene(self.path.trim()) {
+                    Ok(other) => {
+                        self.result = diff(base, &other);
+                        self.other = Some(other);
+                        self.error = None;
+                    }
+                    Err(message) => {
+                        self.other = None;
+                        self.result = SceneDiff::default();
+                        self.error = Some(message);
+                    }
+                }
+            }
+            if ui.button("Clear").clicked() {
+                self.other = None;
+                self.result = SceneDiff::default();
+                self.error = None;
+            }
+        });
+        if let Some(message) = &self.error {
+            ui.colored_label(egui::Color32::LIGHT_RED, egui::RichText::new(message).monospace());
+        }
+        let Some(other) = &self.other else {
+            return;
+        };
+        if self.result.is_empty() {
+            ui.label("No differences.");
+            return;
+        }
+
+        let name = |scene: &Scene, id: usize| {
+            scene.objects.iter().find(|o| o.id == id).map_or(String::new(), |o| o.name.clone())
+        };
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for id in &self.result.added {
+                ui.colored_label(egui::Color32::LIGHT_GREEN, format!("+ #{} {}", id, name(other, *id)));
+            }
+            for id in &self.result.removed {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("- #{} {}", id, name(base, *id)));
+            }
+            for (id, fields) in &self.result.modified {
+                ui.collapsing(format!("~ #{} {}", id, name(base, *id)), |ui| {
+                    for field in fields {
+                        ui.label(field);
+                    }
+                });
+            }
+        });
+    }
+}
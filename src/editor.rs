@@ -1,259 +1,2880 @@
 use eframe::egui;
-use serde::{Deserialize, Serialize};
+use crate::diff::Compare;
+use crate::history::History;
+use crate::keymap::Action;
+use crate::layers;
+use crate::loader::{self, SceneLoad, TextureLoader};
+use crate::model::{default_pivot, default_snap, Bookmark, GameObject, Scene, Session};
+use crate::palette::Palette;
+use crate::path_prompt::{PathAction, PathPrompt};
+use crate::prefs::{EditorSettings, ObjectTemplate, Preferences, PREFS_KEY, SETTINGS_KEY};
+use crate::recovery;
+use crate::relink::Relink;
 use crate::save;
+use crate::sheet::SheetImport;
+use crate::thumbnail;
+use crate::toast::Toasts;
+use crate::transform::{Transform, OBJECT_SIZE};
+use crate::validation::{Fix, Validation};
+use crate::widgets;
+use std::collections::BTreeMap;
 
 pub struct StarEditor {
     selected: Option<usize>,
-    objects: Vec<GameObject>,
+    selection: std::collections::BTreeSet<usize>,
+    scene: Scene,
     zoom: f32,
     dragging: Option<usize>,
     drag_start: Option<egui::Pos2>,
     view_offset: [f32; 2],
+    // 화면 전체 회전 (라디안)
+    view_rotation: f32,
     pan_start: Option<egui::Pos2>,
     image_cache: std::collections::HashMap<String, egui::TextureHandle>,
+    failed_images: std::collections::HashSet<String>,
+    textures: TextureLoader,
+    // 계층 목록용 작은 텍스처는 원본과 따로 디코딩하고 따로 보관
+    thumbs: TextureLoader,
+    thumbnails: thumbnail::ThumbnailWriter,
+    thumb_cache: std::collections::HashMap<String, egui::TextureHandle>,
+    // 씬 뷰에서 마지막으로 그린 프레임 번호 (예산을 넘으면 오래된 것부터 내림)
+    texture_used: std::collections::HashMap<String, u64>,
+    scene_load: Option<SceneLoad>,
+    // 연 직후 화면에 보이는 텍스처를 불러오는 동안: 경로, 가장 많이 기다린 수, 연 프레임
+    texture_progress: Option<(String, usize, u64)>,
+    // 열지 못한 파일과 오류 메시지 (닫을 때까지 창으로 보여줌)
+    load_error: Option<(String, String)>,
+    // 켜면 씬 뷰 클릭으로 선택이 바뀌지 않음 (선택한 오브젝트 드래그는 가능)
+    selection_locked: bool,
+    // 더블클릭으로 들어간 그룹 id. 그 자손만 고르고 편집할 수 있고 나머지는 흐리게
+    entered_group: Option<usize>,
+    // 인스펙터의 상대 위치 입력: 기준 오브젝트 id와 그 위치로부터의 오프셋 (월드 단위)
+    relative_to: Option<usize>,
+    relative_offset: [f32; 2],
+    solo: Option<std::collections::HashSet<usize>>,
+    scroll_to_selected: bool,
+    focus_name: bool,
+    prefs: Preferences,
+    // 격자, 스냅, 표시 옵션처럼 툴바에서 켜고 끄는 상태 (다음 실행 때 그대로)
+    settings: EditorSettings,
+    show_prefs: bool,
+    show_shortcuts: bool,
+    drag_raw: [f32; 2],
+    pixel_zoom_scroll: f32,
+    sheet_import: SheetImport,
+    next_id: usize,
+    toasts: Toasts,
+    scene_rect: egui::Rect,
+    show_layers: bool,
+    show_assets: bool,
+    compare: Compare,
+    recovery_prompt: bool,
+    // 이 빌드보다 새 형식으로 저장된 파일을 열었으면 그 버전
+    newer_format: Option<u32>,
+    // 새 형식 파일을 덮어쓰기 전에 확인할 경로
+    overwrite_prompt: Option<String>,
+    last_snapshot: f64,
+    ping: Option<(usize, f64)>,
+    pinged_selection: Option<usize>,
+    placing: Option<GameObject>,
+    drag_moved: bool,
+    pivot_drag: Option<usize>,
+    group_drag: Option<(GroupHandle, [f32; 2], egui::Pos2)>,
+    staged: Option<(GameObject, GameObject)>,
+    // 지난 프레임 인스펙터 패널 영역 (Enter가 인스펙터 입력칸에서 눌렸는지 판단)
+    inspector_rect: egui::Rect,
+    scene_cursor: Option<[f32; 2]>,
+    clipboard: Vec<GameObject>,
+    pasted_text: Option<String>,
+    validation: Validation,
+    palette: Palette,
+    history: History,
+    edit_label: Option<String>,
+    frame_time: f32,
+    collapsed: std::collections::BTreeSet<usize>,
+    hierarchy_query: String,
+    editing_image_path: bool,
+    show_info: bool,
+    pending_cycle: Option<bool>,
+    current_path: String,
+    saved_ron: String,
+    dirty: bool,
+    path_prompt: PathPrompt,
+    relink: Relink,
 }
 
 impl Default for StarEditor {
     fn default() -> Self {
         Self {
             selected: None,
-            objects: vec![],
+            selection: std::collections::BTreeSet::new(),
+            scene: Scene::default(),
             zoom: 1.0,
             dragging: None,
             drag_start: None,
             view_offset: [0.0, 0.0],
+            view_rotation: 0.0,
             pan_start: None,
             image_cache: std::collections::HashMap::new(),
+            failed_images: std::collections::HashSet::new(),
+            textures: TextureLoader::default(),
+            thumbs: TextureLoader::default(),
+            thumbnails: thumbnail::ThumbnailWriter::default(),
+            thumb_cache: std::collections::HashMap::new(),
+            texture_used: std::collections::HashMap::new(),
+            scene_load: None,
+            texture_progress: None,
+            load_error: None,
+            selection_locked: false,
+            entered_group: None,
+            relative_to: None,
+            relative_offset: [0.0, 0.0],
+            solo: None,
+            scroll_to_selected: false,
+            focus_name: false,
+            prefs: Preferences::default(),
+            settings: EditorSettings::default(),
+            show_prefs: false,
+            show_shortcuts: false,
+            drag_raw: [0.0, 0.0],
+            pixel_zoom_scroll: 0.0,
+            sheet_import: SheetImport::default(),
+            next_id: 0,
+            toasts: Toasts::default(),
+            scene_rect: egui::Rect::NOTHING,
+            show_layers: false,
+            show_assets: false,
+            compare: Compare::default(),
+            recovery_prompt: false,
+            newer_format: None,
+            overwrite_prompt: None,
+            last_snapshot: 0.0,
+            ping: None,
+            pinged_selection: None,
+            placing: None,
+            drag_moved: false,
+            pivot_drag: None,
+            group_drag: None,
+            staged: None,
+            inspector_rect: egui::Rect::NOTHING,
+            scene_cursor: None,
+            clipboard: Vec::new(),
+            pasted_text: None,
+            validation: Validation::default(),
+            palette: Palette::default(),
+            history: History::default(),
+            edit_label: None,
+            frame_time: 0.0,
+            collapsed: std::collections::BTreeSet::new(),
+            hierarchy_query: String::new(),
+            editing_image_path: false,
+            show_info: false,
+            pending_cycle: None,
+            current_path: "scene.ron".to_string(),
+            saved_ron: String::new(),
+            dirty: false,
+            path_prompt: PathPrompt::default(),
+            relink: Relink::default(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct GameObject {
-    id: usize,
-    name: String,
-    position: [f32; 2],
-    rotation: f32,
-    scale: [f32; 2],
-    pub image_path: Option<String>,
+// 파일 관리자에서 파일을 보여줌 (리눅스는 선택 표시가 없어 폴더만 엶)
+fn reveal_in_file_manager(path: &str) -> bool {
+    use std::process::Command;
+
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| std::path::PathBuf::from(path));
+    let result = if cfg!(target_os = "windows") {
+        Command::new("explorer").arg(format!("/select,{}", path.display())).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg("-R").arg(&path).spawn()
+    } else {
+        let folder = path.parent().filter(|p| p.is_dir()).unwrap_or(std::path::Path::new("."));
+        Command::new("xdg-open").arg(folder).spawn()
+    };
+    result.is_ok()
+}
+
+// 피벗은 오브젝트 밖(경첩 등)에도 둘 수 있게 조금 넓게 허용
+const PIVOT_RANGE: std::ops::RangeInclusive<f32> = -1.0..=2.0;
+
+// 여러 개 선택했을 때 묶음 상자의 핸들
+#[derive(Clone, Copy)]
+enum GroupHandle {
+    Scale,
+    Rotate,
+}
+
+impl GroupHandle {
+    // egui에는 회전 커서가 없어 가장 비슷한 모양으로
+    fn cursor(self) -> egui::CursorIcon {
+        match self {
+            GroupHandle::Scale => egui::CursorIcon::ResizeNwSe,
+            GroupHandle::Rotate => egui::CursorIcon::Alias,
+        }
+    }
+}
+
+// 격자 선 간격(화면 px)이 너무 좁아지지 않도록 두 배씩 늘림. 주 격자는 보조 격자 5칸마다
+fn grid_steps(grid: f32, unit: f32) -> (f32, i64) {
+    let mut minor = grid;
+    while minor * unit < 8.0 {
+        minor *= 2.0;
+    }
+    let mut every = 5;
+    while minor * every as f32 * unit < 60.0 {
+        every *= 2;
+    }
+    (minor, every)
+}
+
+// 씬 뷰의 월드 ↔ 화면 변환. 회전은 종이를 돌리듯 화면 가운데를 기준으로 전체에 적용
+#[derive(Clone, Copy)]
+struct View {
+    origin: egui::Pos2,
+    unit: f32,
+    center: egui::Pos2,
+    rotation: egui::emath::Rot2,
+}
+
+impl View {
+    fn new(rect: egui::Rect, offset: [f32; 2], unit: f32, angle: f32) -> Self {
+        Self {
+            origin: rect.min + egui::vec2(offset[0], offset[1]),
+            unit,
+            center: rect.center(),
+            rotation: egui::emath::Rot2::from_angle(angle),
+        }
+    }
+
+    fn angle(self) -> f32 {
+        self.rotation.angle()
+    }
+
+    fn to_screen(self, p: [f32; 2]) -> egui::Pos2 {
+        self.center + self.rotation * (self.origin + egui::vec2(p[0], p[1]) * self.unit - self.center)
+    }
+
+    fn to_world(self, pos: egui::Pos2) -> [f32; 2] {
+        let v = (self.rotation.inverse() * (pos - self.center) + (self.center - self.origin)) / self.unit;
+        [v.x, v.y]
+    }
+
+    // 화면에서 움직인 거리를 월드 단위로
+    fn delta_to_world(self, delta: egui::Vec2) -> [f32; 2] {
+        let v = self.rotation.inverse() * delta / self.unit;
+        [v.x, v.y]
+    }
+
+    // 월드 사각형의 네 꼭짓점 (회전하면 화면에서는 마름모꼴)
+    fn corners(self, b: egui::Rect) -> [egui::Pos2; 4] {
+        [b.left_top(), b.right_top(), b.right_bottom(), b.left_bottom()].map(|p| self.to_screen([p.x, p.y]))
+    }
+
+    // 오브젝트 사각형의 화면 꼭짓점. 그리기, 클릭 판정, 외곽선이 모두 이것을 씀
+    fn object_quad(self, body: Transform) -> [egui::Pos2; 4] {
+        body.corners().map(|p| self.to_screen(p))
+    }
+
+    fn screen_bounds(self, b: egui::Rect) -> egui::Rect {
+        egui::Rect::from_points(&self.corners(b))
+    }
+
+    fn outline(self, painter: &egui::Painter, b: egui::Rect, stroke: egui::Stroke) {
+        let corners = self.corners(b);
+        for k in 0..4 {
+            painter.line_segment([corners[k], corners[(k + 1) % 4]], stroke);
+        }
+    }
+
+    fn fill(self, painter: &egui::Painter, b: egui::Rect, color: egui::Color32) {
+        painter.add(egui::Shape::convex_polygon(self.corners(b).to_vec(), color, egui::Stroke::NONE));
+    }
+}
+
+// 씬 뷰 배경 격자와 위/왼쪽 가장자리 좌표 눈금
+fn draw_grid(painter: &egui::Painter, rect: egui::Rect, view: View, grid: f32, subdivisions: u32) {
+    let (minor, every) = grid_steps(grid, view.unit);
+    let minor_stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(12));
+    let major_stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(30));
+    let font = egui::FontId::monospace(9.0);
+    let label = |v: f32| format!("{}", (v * 100.0).round() / 100.0);
+    // 회전한 화면이 덮는 월드 범위
+    let corners = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()]
+        .map(|p| { let w = view.to_world(p); egui::pos2(w[0], w[1]) });
+    let world = egui::Rect::from_points(&corners);
+    // 눈금 숫자는 화면이 돌아가 있지 않을 때만 가장자리에 붙임
+    let labels = view.angle() == 0.0;
+
+    // 칸 사이의 보조선은 충분히 벌어졌을 때만 가장 옅게 (칸 선과 겹치는 것은 건너뜀)
+    let sub = grid / subdivisions.max(1) as f32;
+    if subdivisions > 1 && sub * view.unit >= 8.0 {
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(6));
+        let n = subdivisions as i64;
+        for k in (world.left() / sub).floor() as i64..=(world.right() / sub).ceil() as i64 {
+            if k % n != 0 {
+                let x = k as f32 * sub;
+                painter.line_segment([view.to_screen([x, world.top()]), view.to_screen([x, world.bottom()])], stroke);
+            }
+        }
+        for k in (world.top() / sub).floor() as i64..=(world.bottom() / sub).ceil() as i64 {
+            if k % n != 0 {
+                let y = k as f32 * sub;
+                painter.line_segment([view.to_screen([world.left(), y]), view.to_screen([world.right(), y])], stroke);
+            }
+        }
+    }
+
+    for k in (world.left() / minor).floor() as i64..=(world.right() / minor).ceil() as i64 {
+        let x = k as f32 * minor;
+        let major = k % every == 0;
+        let (a, b) = (view.to_screen([x, world.top()]), view.to_screen([x, world.bottom()]));
+        painter.line_segment([a, b], if major { major_stroke } else { minor_stroke });
+        if major && labels {
+            painter.text(egui::pos2(a.x + 2.0, rect.top() + 2.0), egui::Align2::LEFT_TOP, label(x), font.clone(), egui::Color32::GRAY);
+        }
+    }
+    for k in (world.top() / minor).floor() as i64..=(world.bottom() / minor).ceil() as i64 {
+        let y = k as f32 * minor;
+        let major = k % every == 0;
+        let (a, b) = (view.to_screen([world.left(), y]), view.to_screen([world.right(), y]));
+        painter.line_segment([a, b], if major { major_stroke } else { minor_stroke });
+        if major && labels {
+            painter.text(egui::pos2(rect.left() + 2.0, a.y + 2.0), egui::Align2::LEFT_TOP, label(y), font.clone(), egui::Color32::GRAY);
+        }
+    }
+}
+
+// 이웃 가장자리에 붙는 거리 (화면 px)
+const EDGE_SNAP: f32 = 6.0;
+
+// 이보다 적게 움직이면 드래그가 아니라 클릭으로 봄
+const DRAG_THRESHOLD: f32 = 3.0;
+
+// 들어간 그룹 밖의 오브젝트를 그릴 때의 불투명도
+const OUTSIDE_GROUP_ALPHA: f32 = 0.25;
+
+// 계층 목록 썸네일 한 변의 최대 픽셀 수
+const HIERARCHY_THUMB: usize = 32;
+
+// 텍스처를 불러오는 동안 자리에 그리는 회색 사각형의 밝기와 반짝임 폭
+const PLACEHOLDER_GRAY: f32 = 90.0;
+const PLACEHOLDER_SHIMMER: f32 = 40.0;
+
+// 핸들 크기는 모두 화면 px라 배율과 상관없이 같은 크기로 잡힘
+// 회전 핸들 점은 잡히는 범위(HANDLE_RADIUS)와 같은 크기로 그림
+const HANDLE_RADIUS: f32 = 6.0;
+const PIVOT_DOT_RADIUS: f32 = 3.0;
+const SCALE_HANDLE_SIZE: f32 = 8.0;
+const ROTATE_HANDLE_OFFSET: f32 = 20.0;
+
+// 픽셀 줌 단계: 1 이상은 정수배, 1 미만은 절반씩
+fn quantize_pixel_zoom(zoom: f32) -> f32 {
+    if zoom >= 1.0 {
+        zoom.round().min(5.0)
+    } else {
+        0.5f32.powf((1.0 / zoom).log2().round()).max(0.125)
+    }
+}
+
+fn step_pixel_zoom(zoom: f32, up: bool) -> f32 {
+    let zoom = quantize_pixel_zoom(zoom);
+    let next = match (up, zoom >= 1.0) {
+        (true, true) => zoom + 1.0,
+        (true, false) => zoom * 2.0,
+        (false, true) if zoom > 1.0 => zoom - 1.0,
+        (false, _) => zoom / 2.0,
+    };
+    quantize_pixel_zoom(next)
+}
+
+// 휠 한 칸 정도의 스크롤 (egui 포인트)
+const PIXEL_ZOOM_SCROLL: f32 = 50.0;
+
+// 트랙패드처럼 여러 프레임에 걸친 스크롤도 모아서 한 칸만큼 넘을 때마다 한 단계씩.
+// 방향이 바뀌면 모아 둔 양은 버림
+fn pixel_zoom_steps(accumulated: &mut f32, scroll: f32) -> i32 {
+    if scroll * *accumulated < 0.0 {
+        *accumulated = 0.0;
+    }
+    *accumulated += scroll;
+    let steps = (*accumulated / PIXEL_ZOOM_SCROLL).trunc();
+    *accumulated -= steps * PIXEL_ZOOM_SCROLL;
+    steps as i32
+}
+
+impl GameObject {
+    pub fn from_template(id: usize, template: &ObjectTemplate) -> Self {
+        Self {
+            id,
+            name: format!("Object {}", id),
+            position: [0.0, 0.0],
+            rotation: 0.0,
+            scale: template.scale,
+            image_path: None,
+            tint: template.tint,
+            tags: template.tags.clone(),
+            properties: template.properties.clone(),
+            uv_rect: None,
+            layer: None,
+            parent: None,
+            pivot: default_pivot(),
+            note: String::new(),
+            flip_x: false,
+            flip_y: false,
+            snap: default_snap(),
+            anchor_to: None,
+            anchor_offset: [0.0, 0.0],
+        }
+    }
 }
 
 impl StarEditor {
-    pub fn load_image(path: &str, ctx: &egui::Context) -> Option<egui::TextureHandle> {
-        use image::io::Reader as ImageReader;
-        use image::GenericImageView;
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut editor = Self::default();
+        if let Some(storage) = cc.storage {
+            editor.prefs = eframe::get_value(storage, PREFS_KEY).unwrap_or_default();
+            editor.settings = eframe::get_value(storage, SETTINGS_KEY).unwrap_or_default();
+        }
+        editor.recovery_prompt = recovery::has_recovery();
+        editor.mark_saved();
+        editor.history.reset("New scene", &editor.scene);
+        editor
+    }
+
+    // adopt가 false면 사본만 쓰고 현재 경로와 변경 상태는 그대로 둠
+    fn save_to(&mut self, path: &str, adopt: bool, ctx: &egui::Context) {
+        let session = if self.prefs.remember_session {
+            Session {
+                selected: self.selection.iter().map(|&i| self.scene.objects[i].id).collect(),
+                collapsed: self.collapsed.iter().copied().collect(),
+                view: Some((self.view_center(), self.zoom)),
+            }
+        } else {
+            Session::default()
+        };
+        // 세션 정보는 파일에만 쓰고 편집 중인 씬은 바꾸지 않음 (변경/되돌리기 기록에 안 잡히도록)
+        let mut scene = self.scene.clone();
+        scene.session = session;
+        scene.version = save::SCENE_VERSION;
+        let saved = if self.prefs.sort_on_save {
+            scene.sort_by_id();
+            let saved = save::save_scene(&scene, path);
+            // 썸네일은 그리기 순서대로
+            scene.apply_draw_order();
+            saved
+        } else {
+            save::save_scene(&scene, path)
+        };
+        // 쓰지 못했으면 저장된 것으로 표시하지 않음
+        if let Err(message) = saved {
+            self.toasts.push(ctx, message);
+            return;
+        }
+        // 썸네일은 이미지를 디코딩해야 하므로 백그라운드에서 만듦
+        self.thumbnails.write(scene, path);
+        if adopt {
+            self.current_path = path.to_string();
+            self.newer_format = None;
+            self.mark_saved();
+        }
+        self.toasts.push(ctx, format!("Saved {}", path));
+    }
 
-        let reader = ImageReader::open(path).ok()?;
-        let img = reader.decode().ok()?;
-        let size = [img.width() as usize, img.height() as usize];
-        let rgba = img.to_rgba8();
-        let pixels = rgba.as_flat_samples();
-        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+    // 새 형식으로 저장된 원본 파일을 덮어쓰려 하면 먼저 확인
+    fn request_save(&mut self, path: &str, ctx: &egui::Context) {
+        if self.newer_format.is_some() && path == self.current_path {
+            self.overwrite_prompt = Some(path.to_string());
+        } else {
+            self.save_to(path, true, ctx);
+        }
+    }
 
-        Some(ctx.load_texture(path.to_string(), color_image, Default::default()))
+    fn mark_saved(&mut self) {
+        self.saved_ron = ron::ser::to_string(&self.scene).unwrap_or_default();
+        self.dirty = false;
+    }
+
+    // 파일은 백그라운드에서 읽고 다 읽히면 finish_open으로 이어짐
+    fn open(&mut self, path: &str) {
+        if let Some(load) = self.scene_load.take() {
+            load.cancel();
+        }
+        self.texture_progress = None;
+        self.scene_load = Some(SceneLoad::start(path));
+    }
+
+    fn finish_open(&mut self, path: &str, scene: Scene, ctx: &egui::Context) {
+        self.textures.clear_queue();
+        self.thumbs.clear_queue();
+        let version = scene.version;
+        self.replace_scene(scene, path, ctx);
+        self.current_path = path.to_string();
+        self.newer_format = (version > save::SCENE_VERSION).then_some(version);
+        self.mark_saved();
+        if self.prefs.remember_session {
+            self.restore_session();
+        }
+        let images: std::collections::HashSet<&String> = self.scene.objects.iter().filter_map(|o| o.image_path.as_ref()).collect();
+        self.toasts.push(ctx, format!("Opened {} ({} objects, {} images)", path, self.scene.objects.len(), images.len()));
+        if let Some(version) = self.newer_format {
+            self.toasts.push(ctx, format!("{} was saved in a newer scene format ({}); some data may not be shown", path, version));
+        }
+        // 저장 기준을 잡은 뒤에 맞춰서 변경된 상태로 남김
+        if self.settings.snap_on_load {
+            let grid = self.snap_step(true);
+            let mut adjusted = 0;
+            for obj in &mut self.scene.objects {
+                let snapped = obj.position.map(|v| (v / grid).round() * grid);
+                if snapped != obj.position {
+                    obj.position = snapped;
+                    adjusted += 1;
+                }
+            }
+            if adjusted > 0 {
+                self.edit_label = Some("Snap to grid on load".to_string());
+                self.toasts.push(ctx, format!("Snapped {} object(s) to the grid", adjusted));
+            }
+        }
+    }
+
+    // 깨진 경로를 새 경로로 바꾸고 텍스처를 다시 불러옴
+    fn apply_relink(&mut self, ctx: &egui::Context) {
+        let to = self.relink.path.trim().to_string();
+        let from = self.relink.from.clone();
+        let mut count = 0;
+        self.edit_label = Some(format!("Relink {}", from));
+        for obj in &mut self.scene.objects {
+            let target = obj.id == self.relink.id || self.relink.all;
+            if target && obj.image_path.as_deref() == Some(from.as_str()) {
+                obj.image_path = Some(to.clone());
+                count += 1;
+            }
+        }
+        self.failed_images.remove(&to);
+        self.ensure_texture(&to, ctx);
+        if self.failed_images.contains(&to) {
+            self.toasts.push(ctx, format!("Relinked {} object(s), but {} failed to load", count, to));
+        } else {
+            self.toasts.push(ctx, format!("Relinked {} object(s) to {}", count, to));
+        }
+    }
+
+    fn replace_scene(&mut self, scene: Scene, path: &str, ctx: &egui::Context) {
+        self.scene = scene;
+        self.clear_selection();
+        // 예전 씬의 인덱스를 가리키는 편집 중 상태도 버림
+        self.dragging = None;
+        self.pivot_drag = None;
+        self.group_drag = None;
+        self.staged = None;
+        self.ping = None;
+        let fixed = self.scene.fix_duplicate_ids();
+        if fixed > 0 {
+            self.toasts.push(ctx, format!("Reassigned {} duplicate object id(s)", fixed));
+        }
+        self.next_id = self.scene.next_id();
+        self.history.reset(format!("Open {}", path), &self.scene);
+    }
+
+    fn select(&mut self, i: usize) {
+        self.selected = Some(i);
+        self.selection.clear();
+        self.selection.insert(i);
+    }
+
+    // 되돌리기 기록의 씬으로 바꾸고 범위를 벗어난 인덱스 상태를 정리
+    fn restore(&mut self, scene: Option<Scene>) {
+        let Some(scene) = scene else {
+            return;
+        };
+        self.scene = scene;
+        let count = self.scene.objects.len();
+        self.selection.retain(|&i| i < count);
+        self.selected = self.selected.filter(|&i| i < count);
+        self.pinged_selection = self.selected;
+        self.dragging = None;
+        self.pivot_drag = None;
+        self.group_drag = None;
+        self.staged = None;
+        self.ping = None;
+        self.next_id = self.next_id.max(self.scene.next_id());
+    }
+
+    // 자식은 지워지지 않는 가장 가까운 조상 밑으로 옮기고 (월드 위치 유지) 선택된 오브젝트를 지움
+    fn delete_selection(&mut self) {
+        let ids: Vec<usize> = self.selection.iter().map(|&i| self.scene.objects[i].id).collect();
+        let orphans: Vec<usize> = self.scene.objects.iter()
+            .filter(|o| !ids.contains(&o.id) && o.parent.is_some_and(|p| ids.contains(&p)))
+            .map(|o| o.id)
+            .collect();
+        for id in orphans {
+            let mut parent = self.scene.index_of(id).and_then(|i| self.scene.objects[i].parent);
+            while let Some(p) = parent.filter(|p| ids.contains(p)) {
+                parent = self.scene.index_of(p).and_then(|i| self.scene.objects[i].parent);
+            }
+            self.scene.reparent(id, parent);
+        }
+        // 지운 오브젝트에 고정돼 있던 오브젝트는 고정을 풀고 지금 월드 위치에 둠
+        let unanchored: Vec<usize> = self.scene.objects.iter()
+            .filter(|o| !ids.contains(&o.id) && o.anchor_to.is_some_and(|a| ids.contains(&a)))
+            .map(|o| o.id)
+            .collect();
+        for id in unanchored {
+            self.scene.set_anchor(id, None);
+        }
+        self.scene.objects.retain(|o| !ids.contains(&o.id));
+        self.clear_selection();
+        self.dragging = None;
+        self.ping = None;
+        self.staged = None;
+    }
+
+    // 선택 묶음 가운데에 빈 오브젝트를 만들고 선택된 오브젝트를 그 밑으로 (월드 위치 유지)
+    fn group_selection(&mut self) {
+        let worlds = self.scene.world_transforms();
+        let Some(bounds) = self.selection.iter()
+            .map(|&i| worlds[i].centered(self.scene.objects[i].pivot).aabb())
+            .reduce(|a, b| a.union(b))
+        else {
+            return;
+        };
+        let ids: Vec<usize> = self.selection.iter().map(|&i| self.scene.objects[i].id).collect();
+        let roots: Vec<usize> = self.selection.iter()
+            .filter(|&&i| self.scene.objects[i].parent.is_none_or(|p| !ids.contains(&p)))
+            .map(|&i| self.scene.objects[i].id)
+            .collect();
+        // 모두 같은 부모 밑에 있었으면 그룹도 그 부모 밑에 둠
+        let parents: std::collections::BTreeSet<Option<usize>> = roots.iter()
+            .filter_map(|&id| self.scene.index_of(id))
+            .map(|i| self.scene.objects[i].parent)
+            .collect();
+        let parent = if parents.len() == 1 { parents.into_iter().next().flatten() } else { None };
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut group = GameObject::from_template(id, &ObjectTemplate::default());
+        group.name = format!("Group {}", id);
+        group.tags.clear();
+        group.properties.clear();
+        let parent_world = parent.and_then(|p| self.scene.index_of(p)).map_or(Transform::default(), |p| worlds[p]);
+        group.position = parent_world.localize(Transform { position: [bounds.center().x, bounds.center().y], ..Transform::default() }).position;
+        group.parent = parent;
+        self.scene.objects.push(group);
+        for root in &roots {
+            self.scene.reparent(*root, Some(id));
+        }
+        self.select(self.scene.objects.len() - 1);
+        self.edit_label = Some(format!("Group {} objects", roots.len()));
+    }
+
+    // 선택된 그룹을 없애고 자식을 그룹의 부모 밑으로 옮김 (지울 때와 같은 규칙)
+    fn ungroup_selection(&mut self) {
+        let groups: Vec<usize> = self.selection.iter()
+            .map(|&i| self.scene.objects[i].id)
+            .filter(|&id| self.scene.objects.iter().any(|o| o.parent == Some(id)))
+            .collect();
+        if groups.is_empty() {
+            return;
+        }
+        let children: Vec<usize> = self.scene.objects.iter()
+            .filter(|o| o.parent.is_some_and(|p| groups.contains(&p)) && !groups.contains(&o.id))
+            .map(|o| o.id)
+            .collect();
+        self.selection = groups.iter().filter_map(|&id| self.scene.index_of(id)).collect();
+        self.delete_selection();
+        self.selection = children.iter().filter_map(|&id| self.scene.index_of(id)).collect();
+        self.selected = self.selection.iter().next_back().copied();
+        self.pinged_selection = self.selected;
+        self.edit_label = Some("Ungroup".to_string());
+    }
+
+    // 밖에서 지워진 오브젝트의 id는 건너뜀
+    fn restore_session(&mut self) {
+        let session = self.scene.session.clone();
+        for id in &session.selected {
+            if let Some(i) = self.scene.index_of(*id) {
+                self.selection.insert(i);
+                self.selected = Some(i);
+            }
+        }
+        self.pinged_selection = self.selected;
+        self.collapsed = session.collapsed.into_iter().filter(|&id| self.scene.index_of(id).is_some()).collect();
+        if let Some((center, zoom)) = session.view {
+            self.set_view(center, zoom);
+        }
+    }
+
+    // 단축키, 명령 팔레트, 버튼이 함께 쓰는 명령 실행
+    fn run_action(&mut self, action: Action, ctx: &egui::Context) {
+        match action {
+            Action::Rename if self.selected.is_some() => self.focus_name = true,
+            Action::Save => {
+                let path = self.current_path.clone();
+                self.request_save(&path, ctx);
+            }
+            Action::SaveAs => self.path_prompt.open(PathAction::SaveAs, &self.current_path),
+            Action::SaveCopy => self.path_prompt.open(PathAction::SaveCopy, &self.current_path),
+            Action::Open => self.path_prompt.open(PathAction::Open, &self.current_path),
+            // 새 오브젝트는 클릭해서 놓을 때까지 커서를 따라다님
+            Action::AddObject => {
+                self.placing = Some(GameObject::from_template(self.next_id, &self.prefs.object_template));
+            }
+            Action::ImportSheet => self.sheet_import.open = true,
+            Action::ExportManifest => self.path_prompt.open(PathAction::ExportManifest, "assets.txt"),
+            Action::ImportCsv => self.path_prompt.open(PathAction::ImportCsv, "objects.csv"),
+            Action::ExportPrefab if !self.selection.is_empty() => {
+                self.path_prompt.open(PathAction::ExportPrefab, "selection.prefab.ron");
+            }
+            Action::InsertPrefab => self.path_prompt.open(PathAction::InsertPrefab, "selection.prefab.ron"),
+            Action::Undo => {
+                let scene = self.history.undo().cloned();
+                self.restore(scene);
+            }
+            Action::Redo => {
+                let scene = self.history.redo().cloned();
+                self.restore(scene);
+            }
+            Action::Delete if !self.selection.is_empty() => self.delete_selection(),
+            Action::Group if !self.selection.is_empty() => self.group_selection(),
+            Action::Ungroup if !self.selection.is_empty() => self.ungroup_selection(),
+            Action::History => self.history.open = !self.history.open,
+            Action::FocusSelected => self.focus_selected(),
+            Action::FrameSelected => self.frame_selected(),
+            Action::FrameAll => {
+                if let Some(bounds) = self.scene.bounds() {
+                    self.frame(bounds);
+                }
+            }
+            // 화면 위치(view_offset)는 그대로 두고 배율만 1:1로
+            Action::ActualSize => self.zoom = 1.0,
+            Action::Duplicate if !self.selection.is_empty() => self.duplicate_selection([1.0, 1.0]),
+            // 원본과 정확히 겹치는 사본을 만들고 사본을 선택
+            Action::DuplicateInPlace if !self.selection.is_empty() => {
+                self.duplicate_selection([0.0, 0.0]);
+                self.edit_label = Some("Duplicate in place".to_string());
+            }
+            Action::Copy if !self.selection.is_empty() => self.copy_selection(ctx),
+            Action::Paste => self.paste(false, ctx),
+            Action::PasteInPlace => self.paste(true, ctx),
+            Action::Solo => self.toggle_solo(),
+            Action::MirrorHorizontal => self.mirror_selection(true),
+            Action::MirrorVertical => self.mirror_selection(false),
+            Action::Validate => self.validation.open = !self.validation.open,
+            Action::Preferences => self.show_prefs = !self.show_prefs,
+            Action::ShortcutHelp => self.show_shortcuts = !self.show_shortcuts,
+            Action::ToggleHierarchy => self.prefs.show_hierarchy = !self.prefs.show_hierarchy,
+            Action::ToggleInspector => self.prefs.show_inspector = !self.prefs.show_inspector,
+            Action::ToggleGrid => {
+                self.settings.show_grid = !self.settings.show_grid;
+                self.toasts.push(ctx, format!("Grid {}", if self.settings.show_grid { "on" } else { "off" }));
+            }
+            Action::ToggleSnap => {
+                self.settings.snap_to_grid = !self.settings.snap_to_grid;
+                self.toasts.push(ctx, format!("Snap to grid {}", if self.settings.snap_to_grid { "on" } else { "off" }));
+            }
+            Action::CommandPalette => self.palette.toggle(),
+            _ => {}
+        }
+    }
+
+    // 선택된 오브젝트만 임시로 보이게 함. 레이어 표시 상태는 건드리지 않음
+    fn toggle_solo(&mut self) {
+        if self.solo.take().is_none() && !self.selection.is_empty() {
+            self.solo = Some(self.selection.iter().map(|&i| self.scene.objects[i].id).collect());
+        }
+    }
+
+    // Ctrl 클릭: 선택에 추가하거나 뺌
+    fn toggle_select(&mut self, i: usize) {
+        if self.selection.remove(&i) {
+            if self.selected == Some(i) {
+                self.selected = self.selection.iter().next().copied();
+            }
+        } else {
+            self.selection.insert(i);
+            self.selected = Some(i);
+        }
+    }
+
+    fn duplicate_selection(&mut self, offset: [f32; 2]) {
+        let mut copies = Vec::new();
+        for &i in &self.selection {
+            let mut copy = self.scene.objects[i].duplicate(self.next_id);
+            self.next_id += 1;
+            copy.position[0] += offset[0];
+            copy.position[1] += offset[1];
+            copies.push(copy);
+        }
+        self.selection.clear();
+        for copy in copies {
+            self.scene.objects.push(copy);
+            self.selection.insert(self.scene.objects.len() - 1);
+        }
+        self.selected = self.selection.iter().next_back().copied();
+    }
+
+    // 선택된 오브젝트들의 월드 변환을 한꺼번에 바꿈. 부모도 선택돼 있으면 부모를 따라가므로 건너뜀
+    fn transform_selection(&mut self, f: impl Fn(Transform) -> Transform) {
+        let worlds = self.scene.world_transforms();
+        let ids: Vec<usize> = self.selection.iter().map(|&i| self.scene.objects[i].id).collect();
+        for &i in &self.selection {
+            let id = self.scene.objects[i].id;
+            if ids.iter().any(|&a| a != id && self.scene.depends_on(id, a)) {
+                continue;
+            }
+            let parent_world = self.scene.parent_world(&worlds, i);
+            let anchor_world = self.scene.anchor_world(&worlds, i);
+            let world = f(worlds[i]);
+            let local = parent_world.localize(world);
+            let obj = &mut self.scene.objects[i];
+            obj.set_world_position(parent_world, anchor_world, world);
+            obj.rotation = local.rotation;
+            obj.scale = local.scale;
+        }
+    }
+
+    // 선택된 오브젝트의 복사본. 선택 밖의 부모와는 연결을 끊고 월드 좌표로 바꿈
+    fn selected_objects(&self) -> Vec<GameObject> {
+        let worlds = self.scene.world_transforms();
+        let ids: Vec<usize> = self.selection.iter().map(|&i| self.scene.objects[i].id).collect();
+        self.selection.iter().map(|&i| {
+            let mut obj = self.scene.objects[i].clone();
+            // 선택 밖의 기준 오브젝트에서도 떼어내고 지금 자리에 둠
+            if obj.anchor_to.is_some_and(|a| !ids.contains(&a)) {
+                obj.anchor_to = None;
+                obj.set_world_position(self.scene.parent_world(&worlds, i), None, worlds[i]);
+            }
+            if obj.parent.is_none_or(|p| !ids.contains(&p)) {
+                let world = worlds[i];
+                obj.position = world.position;
+                obj.rotation = world.rotation;
+                obj.scale = world.scale;
+                obj.parent = None;
+            }
+            obj
+        }).collect()
+    }
+
+    // 선택된 오브젝트를 묶음 중심 기준 좌표로 저장
+    fn export_prefab(&mut self, path: &str, ctx: &egui::Context) {
+        let mut objects = self.selected_objects();
+        let Some(center) = (Scene { objects: objects.clone(), ..Scene::default() }).bounds().map(|b| b.center()) else {
+            return;
+        };
+        for obj in objects.iter_mut().filter(|o| o.parent.is_none()) {
+            obj.position = [obj.position[0] - center.x, obj.position[1] - center.y];
+        }
+        match save::save_prefab(&objects, path) {
+            Ok(()) => self.toasts.push(ctx, format!("Exported {} object(s) to {}", objects.len(), path)),
+            Err(message) => self.toasts.push(ctx, message),
+        }
+    }
+
+    fn export_manifest(&mut self, path: &str, ctx: &egui::Context) {
+        let assets = save::asset_manifest(&self.scene);
+        let missing = assets.iter().filter(|(_, exists)| !exists).count();
+        if !save::save_manifest(&assets, path) {
+            self.toasts.push(ctx, format!("Could not write {}", path));
+        } else if missing > 0 {
+            self.toasts.push(ctx, format!("Exported {} asset(s) to {}, {} missing on disk", assets.len(), path, missing));
+        } else {
+            self.toasts.push(ctx, format!("Exported {} asset(s) to {}", assets.len(), path));
+        }
+    }
+
+    // 줄마다 기본 오브젝트 설정으로 새 오브젝트를 만들고 적힌 값만 덮어씀
+    fn import_csv(&mut self, path: &str, ctx: &egui::Context) {
+        let (rows, skipped) = match save::load_csv(path) {
+            Ok(result) => result,
+            Err(message) => {
+                self.toasts.push(ctx, message);
+                return;
+            }
+        };
+        self.clear_selection();
+        for row in &rows {
+            let id = self.next_id;
+            self.next_id += 1;
+            let mut obj = GameObject::from_template(id, &self.prefs.object_template);
+            if let Some(name) = &row.name {
+                obj.name = name.clone();
+            }
+            obj.position = row.position;
+            obj.rotation = row.rotation.unwrap_or(obj.rotation);
+            obj.scale = [0, 1].map(|k| row.scale[k].unwrap_or(obj.scale[k]));
+            obj.image_path = row.image_path.clone();
+            self.scene.objects.push(obj);
+            self.selection.insert(self.scene.objects.len() - 1);
+        }
+        self.selected = self.selection.iter().next_back().copied();
+        self.edit_label = Some(format!("Import CSV {}", path));
+        if skipped.is_empty() {
+            self.toasts.push(ctx, format!("Imported {} object(s) from {}", rows.len(), path));
+        } else {
+            let lines: Vec<String> = skipped.iter().map(|n| n.to_string()).collect();
+            self.toasts.push(ctx, format!("Imported {} object(s), skipped malformed line(s) {}", rows.len(), lines.join(", ")));
+        }
+    }
+
+    // 새 id를 받아 커서 위치(없으면 화면 가운데)에 놓음
+    fn insert_prefab(&mut self, path: &str, ctx: &egui::Context) {
+        let Some(objects) = save::load_prefab(path) else {
+            self.toasts.push(ctx, format!("Could not read prefab {}", path));
+            return;
+        };
+        let at = self.scene_cursor.unwrap_or_else(|| self.view_center());
+        let count = self.insert_objects(objects, at);
+        self.edit_label = Some(format!("Insert prefab {}", path));
+        self.toasts.push(ctx, format!("Inserted {} object(s) from {}", count, path));
+    }
+
+    // 선택을 씬 클립보드에 넣고 다른 창에서도 붙일 수 있도록 시스템 클립보드에 RON으로 복사
+    fn copy_selection(&mut self, ctx: &egui::Context) {
+        self.clipboard = self.selected_objects();
+        if let Ok(ron) = ron::ser::to_string(&self.clipboard) {
+            ctx.output_mut(|o| o.copied_text = ron);
+        }
+        self.toasts.push(ctx, format!("Copied {} object(s)", self.clipboard.len()));
+    }
+
+    // 제자리 붙여넣기는 복사한 좌표 그대로, 아니면 묶음 중심을 커서(없으면 화면 가운데)에 맞춤
+    fn paste(&mut self, in_place: bool, ctx: &egui::Context) {
+        let objects = self.pasted_text.take()
+            .and_then(|text| save::parse_prefab(&text))
+            .unwrap_or_else(|| self.clipboard.clone());
+        let Some(center) = (Scene { objects: objects.clone(), ..Scene::default() }).bounds().map(|b| b.center()) else {
+            return;
+        };
+        let offset = if in_place {
+            [0.0, 0.0]
+        } else {
+            let at = self.scene_cursor.unwrap_or_else(|| self.view_center());
+            [at[0] - center.x, at[1] - center.y]
+        };
+        let count = self.insert_objects(objects, offset);
+        self.edit_label = Some(if in_place { "Paste in place" } else { "Paste" }.to_string());
+        self.toasts.push(ctx, format!("Pasted {} object(s)", count));
+    }
+
+    // 새 id로 바꿔 씬에 넣고 선택. 최상위 오브젝트는 offset만큼 옮김
+    fn insert_objects(&mut self, objects: Vec<GameObject>, at: [f32; 2]) -> usize {
+        let ids: BTreeMap<usize, usize> = objects.iter().enumerate().map(|(n, o)| (o.id, self.next_id + n)).collect();
+        self.next_id += objects.len();
+        self.clear_selection();
+        for mut obj in objects {
+            obj.id = ids[&obj.id];
+            obj.parent = obj.parent.and_then(|p| ids.get(&p).copied());
+            obj.anchor_to = obj.anchor_to.and_then(|a| ids.get(&a).copied());
+            if obj.parent.is_none() && obj.anchor_to.is_none() {
+                obj.position = [obj.position[0] + at[0], obj.position[1] + at[1]];
+            }
+            // 이 씬에 없는 레이어는 떼어냄
+            if obj.layer.is_some_and(|l| !self.scene.layers.iter().any(|layer| layer.id == l)) {
+                obj.layer = None;
+            }
+            self.scene.objects.push(obj);
+            self.selection.insert(self.scene.objects.len() - 1);
+        }
+        self.selected = self.selection.iter().next_back().copied();
+        ids.len()
+    }
+
+    // 선택한 오브젝트 모두에 같은 이미지 경로 (같은 캐시 키라 텍스처는 하나)
+    fn set_selection_image(&mut self, path: Option<String>, rename: bool) {
+        self.edit_label = Some(match self.selection.len() {
+            1 => "Set image".to_string(),
+            n => format!("Set image on {} objects", n),
+        });
+        for &i in &self.selection {
+            let obj = &mut self.scene.objects[i];
+            obj.set_image_path(path.clone());
+            if rename {
+                obj.name_after_image();
+            }
+        }
+    }
+
+    // 선택 묶음의 중심(또는 월드 원점)을 기준으로 뒤집고 스프라이트 방향도 함께 뒤집음
+    fn mirror_selection(&mut self, horizontal: bool) {
+        let worlds = self.scene.world_transforms();
+        let Some(bounds) = self.selection.iter()
+            .map(|&i| worlds[i].centered(self.scene.objects[i].pivot).aabb())
+            .reduce(|a, b| a.union(b))
+        else {
+            return;
+        };
+        self.edit_label = Some(if horizontal { "Mirror horizontally" } else { "Mirror vertically" }.to_string());
+        let axis = if self.settings.mirror_about_origin { egui::Pos2::ZERO } else { bounds.center() };
+        let targets: BTreeMap<usize, Transform> = self.selection.iter().map(|&i| {
+            let world = worlds[i];
+            let position = if horizontal {
+                [2.0 * axis.x - world.position[0], world.position[1]]
+            } else {
+                [world.position[0], 2.0 * axis.y - world.position[1]]
+            };
+            (i, Transform { position, rotation: -world.rotation, ..world })
+        }).collect();
+        // 부모부터 옮겨야 자식의 로컬 좌표가 맞게 계산됨
+        for (i, _) in self.scene.tree_order() {
+            let Some(target) = targets.get(&i) else {
+                continue;
+            };
+            let worlds = self.scene.world_transforms();
+            let parent_world = self.scene.parent_world(&worlds, i);
+            let anchor_world = self.scene.anchor_world(&worlds, i);
+            let local = parent_world.localize(*target);
+            let obj = &mut self.scene.objects[i];
+            obj.set_world_position(parent_world, anchor_world, *target);
+            obj.rotation = local.rotation;
+            if horizontal {
+                obj.flip_x = !obj.flip_x;
+                obj.pivot[0] = 1.0 - obj.pivot[0];
+            } else {
+                obj.flip_y = !obj.flip_y;
+                obj.pivot[1] = 1.0 - obj.pivot[1];
+            }
+        }
+    }
+
+    // 같은 자리에 겹친 오브젝트를 원형으로 조금씩 벌려 놓음
+    fn separate(&mut self, indices: &[usize]) {
+        self.edit_label = Some("Separate stacked objects".to_string());
+        let worlds = self.scene.world_transforms();
+        let radius = self.settings.grid_size * 0.5;
+        for (n, &i) in indices.iter().enumerate() {
+            let angle = std::f32::consts::TAU * n as f32 / indices.len() as f32;
+            let world = worlds[i];
+            let position = [world.position[0] + radius * angle.cos(), world.position[1] + radius * angle.sin()];
+            let (parent_world, anchor_world) = (self.scene.parent_world(&worlds, i), self.scene.anchor_world(&worlds, i));
+            self.scene.objects[i].set_world_position(parent_world, anchor_world, Transform { position, ..world });
+        }
+    }
+
+    // 멀리 날아간 오브젝트를 화면 가운데로 모으고 선택 (겹치지 않게 한 칸씩 비켜 놓음)
+    fn bring_back(&mut self, indices: &[usize]) {
+        self.edit_label = Some(format!("Bring back {} stray object(s)", indices.len()));
+        let worlds = self.scene.world_transforms();
+        let center = self.view_center();
+        // 부모나 기준 오브젝트가 함께 옮겨지는 오브젝트는 따라오므로 건너뜀
+        let ids: Vec<usize> = indices.iter().map(|&i| self.scene.objects[i].id).collect();
+        let roots: Vec<usize> = indices.iter().copied()
+            .filter(|&i| !ids.iter().any(|&a| a != self.scene.objects[i].id && self.scene.depends_on(self.scene.objects[i].id, a)))
+            .collect();
+        for (n, &i) in roots.iter().enumerate() {
+            let position = [center[0] + n as f32 * self.settings.grid_size, center[1]];
+            let (parent_world, anchor_world) = (self.scene.parent_world(&worlds, i), self.scene.anchor_world(&worlds, i));
+            self.scene.objects[i].set_world_position(parent_world, anchor_world, Transform { position, ..worlds[i] });
+        }
+        self.clear_selection();
+        self.selection.extend(indices.iter().copied());
+        self.selected = indices.last().copied();
+    }
+
+    // 그리기 순서(Vec 순서)를 바꾸고 인덱스 기반 상태도 함께 맞춤
+    fn swap_objects(&mut self, a: usize, b: usize) {
+        self.scene.objects.swap(a, b);
+        let remap = |i: usize| if i == a { b } else if i == b { a } else { i };
+        self.selected = self.selected.map(remap);
+        self.selection = self.selection.iter().map(|&i| remap(i)).collect();
+        self.dragging = self.dragging.map(remap);
+        self.pinged_selection = self.pinged_selection.map(remap);
+        if let Some((i, _)) = &mut self.ping {
+            *i = remap(*i);
+        }
+    }
+
+    // 보조선 간격, coarse면 격자 한 칸
+    fn snap_step(&self, coarse: bool) -> f32 {
+        if coarse {
+            self.settings.grid_size
+        } else {
+            self.settings.grid_size / self.settings.grid_subdivisions.max(1) as f32
+        }
+    }
+
+    fn clear_selection(&mut self) {
+        self.selected = None;
+        self.selection.clear();
+    }
+
+    // 선택된 오브젝트들의 월드 회전/스케일을 활성 오브젝트에 맞춤
+    fn match_transform(&mut self, rotation: bool, scale: bool) {
+        let Some(active) = self.selected else {
+            return;
+        };
+        let worlds = self.scene.world_transforms();
+        let target = worlds[active];
+        for &i in &self.selection {
+            if i == active {
+                continue;
+            }
+            let parent = self.scene.parent_world(&worlds, i);
+            let local = parent.localize(Transform {
+                position: worlds[i].position,
+                rotation: if rotation { target.rotation } else { worlds[i].rotation },
+                scale: if scale { target.scale } else { worlds[i].scale },
+            });
+            let obj = &mut self.scene.objects[i];
+            obj.rotation = local.rotation;
+            obj.scale = local.scale;
+        }
+    }
+
+    // 줌은 그대로 두고 선택된 오브젝트를 화면 가운데로
+    fn focus_selected(&mut self) {
+        let Some(i) = self.selected.filter(|&i| i < self.scene.objects.len()) else {
+            return;
+        };
+        let position = self.scene.world_transforms()[i].position;
+        self.set_view(position, self.zoom);
+    }
+
+    // 선택한 오브젝트 전체가 여백을 남기고 화면에 들어오도록
+    fn frame_selected(&mut self) {
+        let worlds = self.scene.world_transforms();
+        let bounds = self.selection.iter().chain(&self.selected)
+            .filter(|&&i| i < self.scene.objects.len())
+            .map(|&i| worlds[i].centered(self.scene.objects[i].pivot).aabb())
+            .reduce(|a, b| a.union(b));
+        if let Some(bounds) = bounds {
+            self.frame(bounds);
+        }
+    }
+
+    // 월드 영역이 설정한 여백을 남기고 화면에 들어오도록 확대/이동. 화면 회전도 고려
+    fn frame(&mut self, bounds: egui::Rect) {
+        let margin = self.prefs.frame_margin / 100.0;
+        let available = self.scene_rect.size() * (1.0 - 2.0 * margin);
+        let (sin, cos) = self.view_rotation.sin_cos();
+        let (w, h) = (bounds.width(), bounds.height());
+        let size = egui::vec2(w * cos.abs() + h * sin.abs(), w * sin.abs() + h * cos.abs()) * self.scene.pixels_per_unit;
+        let zoom = (available.x / size.x.max(f32::EPSILON)).min(available.y / size.y.max(f32::EPSILON));
+        self.set_view([bounds.center().x, bounds.center().y], zoom);
+    }
+
+    // 화면 가운데에 보이는 월드 좌표
+    fn view_center(&self) -> [f32; 2] {
+        let half = self.scene_rect.size() / 2.0;
+        [
+            (half.x - self.view_offset[0]) / (self.scene.pixels_per_unit * self.zoom),
+            (half.y - self.view_offset[1]) / (self.scene.pixels_per_unit * self.zoom),
+        ]
+    }
+
+    fn set_view(&mut self, center: [f32; 2], zoom: f32) {
+        self.zoom = zoom.clamp(0.1, 5.0);
+        let half = self.scene_rect.size() / 2.0;
+        self.view_offset = [
+            half.x - center[0] * self.scene.pixels_per_unit * self.zoom,
+            half.y - center[1] * self.scene.pixels_per_unit * self.zoom,
+        ];
+    }
+
+    // 앞의 아홉 개는 숫자 키 1–9로 바로 이동
+    fn bookmarks_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button("🔖 Views", |ui| {
+            let mut jump = None;
+            let mut remove = None;
+            for (n, bookmark) in self.scene.bookmarks.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let key = if n < 9 { format!("{}", n + 1) } else { " ".to_string() };
+                    if ui.button(format!("{} ▶", key)).on_hover_text("Go to this view").clicked() {
+                        jump = Some(n);
+                    }
+                    ui.add(egui::TextEdit::singleline(&mut bookmark.name).desired_width(120.0));
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(n);
+                    }
+                });
+            }
+            if self.scene.bookmarks.is_empty() {
+                ui.label("No saved views.");
+            }
+            ui.separator();
+            if ui.button("Add Current View").clicked() {
+                let center = self.view_center();
+                let name = format!("View {}", self.scene.bookmarks.len() + 1);
+                self.edit_label = Some(format!("Add view bookmark {}", name));
+                self.scene.bookmarks.push(Bookmark { name, center, zoom: self.zoom });
+            }
+            if let Some(n) = jump {
+                self.go_to_bookmark(n);
+                ui.close_menu();
+            }
+            if let Some(n) = remove {
+                let bookmark = self.scene.bookmarks.remove(n);
+                self.edit_label = Some(format!("Remove view bookmark {}", bookmark.name));
+            }
+        });
+    }
+
+    fn go_to_bookmark(&mut self, n: usize) {
+        if let Some(bookmark) = self.scene.bookmarks.get(n) {
+            let (center, zoom) = (bookmark.center, bookmark.zoom);
+            self.set_view(center, zoom);
+        }
+    }
+
+    fn assets_ui(&mut self, ui: &mut egui::Ui) {
+        let mut refs: BTreeMap<&str, usize> = BTreeMap::new();
+        for obj in &self.scene.objects {
+            if let Some(path) = obj.image_path.as_deref().filter(|p| !p.is_empty()) {
+                *refs.entry(path).or_default() += 1;
+            }
+        }
+        let mut paths: Vec<&str> = self.image_cache.keys().map(String::as_str)
+            .chain(self.failed_images.iter().map(String::as_str))
+            .chain(refs.keys().copied())
+            .collect();
+        paths.sort_unstable();
+        paths.dedup();
+
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            egui::Grid::new("assets").num_columns(3).striped(true).show(ui, |ui| {
+                ui.strong("Path");
+                ui.strong("Size");
+                ui.strong("Refs");
+                ui.end_row();
+                for path in &paths {
+                    ui.label(*path);
+                    match self.image_cache.get(*path) {
+                        Some(tex) => ui.label(format!("{} x {}", tex.size()[0], tex.size()[1])),
+                        None if self.textures.is_loading(path) => ui.label("loading…"),
+                        None => ui.colored_label(egui::Color32::LIGHT_RED, "missing"),
+                    };
+                    ui.label(refs.get(path).copied().unwrap_or(0).to_string());
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.label(format!("{} texture(s) loaded, {}", self.image_cache.len(), self.texture_memory_text()));
+        if ui.button("Unload Unused").clicked() {
+            let used: std::collections::HashSet<String> = refs.keys().map(|p| p.to_string()).collect();
+            self.image_cache.retain(|path, _| used.contains(path));
+            self.failed_images.retain(|path| used.contains(path));
+        }
+        if ui.button("Export Manifest...").on_hover_text("Write every referenced asset to a file, flagging missing ones").clicked() {
+            self.run_action(Action::ExportManifest, &ui.ctx().clone());
+        }
+    }
+
+    fn info_ui(&self, ui: &mut egui::Ui) {
+        let objects = &self.scene.objects;
+        let with_image = objects.iter().filter(|o| o.image_path.is_some()).count();
+        let unique: std::collections::HashSet<&str> =
+            objects.iter().filter_map(|o| o.image_path.as_deref()).collect();
+        let bounds = self.scene.bounds();
+
+        egui::Grid::new("scene_info").num_columns(2).show(ui, |ui| {
+            ui.label("Objects:");
+            ui.label(objects.len().to_string());
+            ui.end_row();
+            ui.label("With image:");
+            ui.label(with_image.to_string());
+            ui.end_row();
+            ui.label("Without image:");
+            ui.label((objects.len() - with_image).to_string());
+            ui.end_row();
+            ui.label("Unique textures:");
+            ui.label(unique.len().to_string());
+            ui.end_row();
+            ui.label("Texture memory:");
+            ui.label(self.texture_memory_text());
+            ui.end_row();
+            ui.label("Scene bounds:");
+            match bounds {
+                Some(b) => ui.label(format!(
+                    "{:.2} x {:.2} units\n({:.2}, {:.2}) – ({:.2}, {:.2})",
+                    b.width(), b.height(), b.min.x, b.min.y, b.max.x, b.max.y
+                )),
+                None => ui.label("—"),
+            };
+            ui.end_row();
+        });
+    }
+
+    fn import_sheet(&mut self, ctx: &egui::Context) {
+        let path = self.sheet_import.path.trim().to_string();
+        self.ensure_texture(&path, ctx);
+        let Some(tex) = self.image_cache.get(&path) else {
+            return;
+        };
+
+        let [tw, th] = self.sheet_import.tile;
+        let stem = std::path::Path::new(&path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Tile".to_string());
+        let template = &self.prefs.object_template;
+        let scale = [template.scale[0], template.scale[0] * th as f32 / tw as f32];
+
+        for (row, col, uv) in self.sheet_import.slice(tex.size()) {
+            let id = self.next_id;
+            self.next_id += 1;
+            let mut obj = GameObject::from_template(id, template);
+            obj.name = format!("{} {}_{}", stem, row, col);
+            obj.position = [col as f32 * scale[0] * OBJECT_SIZE, row as f32 * scale[1] * OBJECT_SIZE];
+            obj.scale = scale;
+            obj.image_path = Some(path.clone());
+            obj.uv_rect = Some(uv);
+            self.scene.objects.push(obj);
+        }
+    }
+
+    pub fn load_image(path: &str, ctx: &egui::Context, options: egui::TextureOptions) -> Option<egui::TextureHandle> {
+        let color_image = loader::decode_image(path, None)?;
+        Some(ctx.load_texture(path.to_string(), color_image, options))
+    }
+
+    fn texture_memory(&self) -> usize {
+        self.image_cache.values().map(|t| t.size()[0] * t.size()[1] * 4).sum()
+    }
+
+    fn texture_memory_text(&self) -> String {
+        let mb = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+        match self.prefs.texture_budget() {
+            Some(budget) => format!("{:.2} / {:.0} MB", mb(self.texture_memory()), mb(budget)),
+            None => format!("{:.2} MB", mb(self.texture_memory())),
+        }
+    }
+
+    // 예산을 넘으면 직전 프레임에 그리지 않은 텍스처 중 가장 오래된 것부터 내림.
+    // 내린 텍스처는 다시 화면에 들어오면 새로 불러옴
+    fn enforce_texture_budget(&mut self, frame: u64) {
+        let Some(budget) = self.prefs.texture_budget() else {
+            return;
+        };
+        let mut memory = self.texture_memory();
+        while memory > budget {
+            let oldest = self.image_cache.keys()
+                .map(|p| (self.texture_used.get(p).copied().unwrap_or(0), p))
+                .filter(|(used, _)| used + 1 < frame)
+                .min()
+                .map(|(_, p)| p.clone());
+            let Some(path) = oldest else {
+                break;
+            };
+            if let Some(tex) = self.image_cache.remove(&path) {
+                memory -= tex.size()[0] * tex.size()[1] * 4;
+            }
+            self.texture_used.remove(&path);
+        }
+    }
+
+    // 실패한 경로는 매 프레임 다시 읽지 않도록 기록
+    fn ensure_texture(&mut self, path: &str, ctx: &egui::Context) {
+        if path.is_empty() || self.image_cache.contains_key(path) || self.failed_images.contains(path) {
+            return;
+        }
+        match StarEditor::load_image(path, ctx, self.prefs.texture_options()) {
+            Some(tex) => {
+                self.image_cache.insert(path.to_string(), tex);
+            }
+            None => {
+                self.failed_images.insert(path.to_string());
+            }
+        }
     }
 }
 
 impl eframe::App for StarEditor {
+    // Tab은 egui가 포커스 이동에 쓰므로, 아무것도 포커스되지 않았을 때는 먼저 가로챔
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        if ctx.wants_keyboard_input() || self.prefs.keymap.is_recording() {
+            return;
+        }
+        // egui는 복사/붙여넣기 단축키를 키 이벤트 대신 Copy/Paste 이벤트로 바꾸므로 다시 키로 되돌림
+        let modifiers = raw_input.modifiers;
+        for event in &mut raw_input.events {
+            let key = match event {
+                egui::Event::Copy => egui::Key::C,
+                egui::Event::Paste(text) => {
+                    self.pasted_text = Some(std::mem::take(text));
+                    egui::Key::V
+                }
+                _ => continue,
+            };
+            *event = egui::Event::Key { key, physical_key: None, pressed: true, repeat: false, modifiers };
+        }
+
+        let keymap = &self.prefs.keymap;
+        let pending = &mut self.pending_cycle;
+        raw_input.events.retain(|e| {
+            if keymap.matches_event(e, Action::CycleNext) {
+                *pending = Some(true);
+                false
+            } else if keymap.matches_event(e, Action::CyclePrevious) {
+                *pending = Some(false);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, PREFS_KEY, &self.prefs);
+        eframe::set_value(storage, SETTINGS_KEY, &self.settings);
+    }
+
    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::SidePanel::left("hierarchy").show(ctx, |ui| {
+        ctx.tessellation_options_mut(|options| options.feathering = self.prefs.antialiasing);
+
+        // 텍스트 입력 중에는 단축키를 처리하지 않음
+        let keyboard_free = !ctx.wants_keyboard_input() && !self.prefs.keymap.is_recording();
+        let keymap = self.prefs.keymap.clone();
+        // 인스펙터 입력칸에서 누른 Enter는 적용 모드의 Apply로만 씀 (이름 바꾸기로 넘어가지 않음)
+        let focus_at_start = ctx.memory(|m| m.focused());
+        let inspector_enter = ctx.input(|i| i.key_pressed(egui::Key::Enter))
+            && focus_at_start.and_then(|id| ctx.read_response(id)).is_some_and(|r| self.inspector_rect.contains_rect(r.rect));
+
+        if keyboard_free {
+            let pressed: Vec<Action> = ctx.input(|i| {
+                Action::ALL.iter().copied()
+                    .filter(|&a| a.is_command() && keymap.pressed(i, a))
+                    .filter(|&a| !(inspector_enter && a == Action::Rename))
+                    .collect()
+            });
+            for action in pressed {
+                self.run_action(action, ctx);
+            }
+        }
+        // 숫자 키 1–9: 저장한 카메라 위치로 이동
+        if keyboard_free {
+            const DIGITS: [egui::Key; 9] = [
+                egui::Key::Num1, egui::Key::Num2, egui::Key::Num3, egui::Key::Num4, egui::Key::Num5,
+                egui::Key::Num6, egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+            ];
+            let digit = ctx.input(|i| {
+                DIGITS.iter().position(|&key| i.modifiers.is_none() && i.key_pressed(key))
+            });
+            if let Some(n) = digit {
+                self.go_to_bookmark(n);
+            }
+        }
+        // 붙여넣기 단축키로 쓰이지 않은 시스템 클립보드 내용은 버림
+        self.pasted_text = None;
+        if let Some(action) = self.palette.show(ctx, &keymap) {
+            self.run_action(action, ctx);
+        }
+
+        let cycle = self.pending_cycle.take();
+        if keyboard_free && !self.palette.is_open() && !self.scene.objects.is_empty() {
+            let (up, down) = ctx.input(|i| {
+                (
+                    keymap.pressed(i, Action::SelectPrevious),
+                    keymap.pressed(i, Action::SelectNext),
+                )
+            });
+            // 계층 트리에 보이는 순서대로 이동
+            let order: Vec<usize> = self.scene.tree_order().into_iter().map(|(i, _)| i).collect();
+            let last = order.len() - 1;
+            let current = self.selected.and_then(|s| order.iter().position(|&i| i == s));
+            // Tab / Shift+Tab은 끝에서 처음으로 돌아감
+            match cycle {
+                Some(true) => {
+                    self.select(order[current.map_or(0, |p| if p == last { 0 } else { p + 1 })]);
+                    self.scroll_to_selected = true;
+                }
+                Some(false) => {
+                    self.select(order[current.map_or(last, |p| if p == 0 { last } else { p - 1 })]);
+                    self.scroll_to_selected = true;
+                }
+                None => {}
+            }
+            if up {
+                self.select(order[current.map_or(last, |p| p.saturating_sub(1))]);
+                self.scroll_to_selected = true;
+            }
+            if down {
+                self.select(order[current.map_or(0, |p| (p + 1).min(last))]);
+                self.scroll_to_selected = true;
+            }
+        }
+
+        egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.settings.show_overlaps, "Show Overlaps");
+                ui.checkbox(&mut self.settings.show_ids, "Show IDs").on_hover_text("Draw each object's id and index");
+                ui.checkbox(&mut self.settings.show_centers, "Show Centers").on_hover_text("Draw a dot at each object's origin");
+                ui.checkbox(&mut self.settings.show_links, "Show Links").on_hover_text("Draw a line from each child to its parent's origin");
+                let solo = ui.add_enabled(
+                    self.solo.is_some() || !self.selection.is_empty(),
+                    egui::SelectableLabel::new(self.solo.is_some(), "Solo"),
+                );
+                if solo.on_hover_text("Temporarily hide everything except the selection").clicked() {
+                    self.toggle_solo();
+                }
+                if ui.selectable_label(self.selection_locked, "🔒 Lock Selection")
+                    .on_hover_text("Ignore clicks in the scene view so the selection stays put")
+                    .clicked()
+                {
+                    self.selection_locked = !self.selection_locked;
+                }
+                if ui.checkbox(&mut self.settings.pixel_zoom, "Pixel Zoom").changed() && self.settings.pixel_zoom {
+                    self.zoom = quantize_pixel_zoom(self.zoom);
+                }
+                ui.separator();
+                ui.checkbox(&mut self.settings.show_grid, "Grid");
+                ui.checkbox(&mut self.settings.snap_to_grid, "Snap");
+                ui.checkbox(&mut self.settings.snap_edges, "Edge snap")
+                    .on_hover_text("Snap dragged objects flush against neighbouring edges");
+                ui.add(egui::DragValue::new(&mut self.settings.grid_size).speed(0.1).clamp_range(0.1..=100.0).prefix("grid "));
+                ui.add(egui::DragValue::new(&mut self.settings.grid_subdivisions).speed(0.1).clamp_range(1..=16).prefix("÷ "))
+                    .on_hover_text("Minor lines per grid cell. Snapping lands on them; hold Shift to snap to whole cells");
+                ui.checkbox(&mut self.settings.limit_pan, "Limit pan")
+                    .on_hover_text("Keep part of the scene in view while panning");
+                ui.checkbox(&mut self.settings.snap_on_load, "Snap on load")
+                    .on_hover_text("Round object positions to the grid when opening a scene");
+                ui.separator();
+                ui.add(
+                    egui::DragValue::new(&mut self.scene.pixels_per_unit)
+                        .speed(0.5)
+                        .clamp_range(1.0..=1000.0)
+                        .suffix(" px/unit"),
+                ).on_hover_text("Screen pixels per world unit at 100% zoom");
+                ui.separator();
+                ui.label("Background:");
+                ui.color_edit_button_srgba_unmultiplied(&mut self.scene.background_color);
+                ui.separator();
+                if ui.button("Layers").clicked() {
+                    self.show_layers = !self.show_layers;
+                }
+                if ui.button("Assets").clicked() {
+                    self.show_assets = !self.show_assets;
+                }
+                if ui.button("Compare").clicked() {
+                    self.compare.open = !self.compare.open;
+                }
+                if ui.button("Info").clicked() {
+                    self.show_info = !self.show_info;
+                }
+                if ui.button("Validate").clicked() {
+                    self.run_action(Action::Validate, ctx);
+                }
+                if ui.button("History").clicked() {
+                    self.run_action(Action::History, ctx);
+                }
+                if ui.button("⚙ Preferences").clicked() {
+                    self.run_action(Action::Preferences, ctx);
+                }
+            });
+        });
+
+        egui::Window::new("Layers")
+            .open(&mut self.show_layers)
+            .show(ctx, |ui| layers::layers_ui(ui, &mut self.scene));
+
+        let mut show_assets = self.show_assets;
+        egui::Window::new("Assets")
+            .open(&mut show_assets)
+            .show(ctx, |ui| self.assets_ui(ui));
+        self.show_assets = show_assets;
+
+        let mut show_compare = self.compare.open;
+        egui::Window::new("Compare Scenes")
+            .open(&mut show_compare)
+            .show(ctx, |ui| self.compare.ui(ui, &self.scene));
+        self.compare.open = show_compare;
+
+        let mut show_info = self.show_info;
+        egui::Window::new("Scene Info")
+            .open(&mut show_info)
+            .resizable(false)
+            .show(ctx, |ui| self.info_ui(ui));
+        self.show_info = show_info;
+
+        // 화면 밖 오브젝트의 이미지도 검사할 수 있도록 검증 창이 열려 있으면 모두 불러옴
+        if self.validation.open {
+            for path in self.scene.objects.iter().filter_map(|o| o.image_path.as_ref()) {
+                if !self.image_cache.contains_key(path) && !self.failed_images.contains(path) {
+                    self.textures.request(path);
+                }
+            }
+        }
+        let mut fix = None;
+        let mut open = self.validation.open;
+        egui::Window::new("Validation")
+            .open(&mut open)
+            .show(ctx, |ui| fix = self.validation.ui(ui, &self.scene, &self.failed_images));
+        self.validation.open = open;
+        match fix {
+            Some(Fix::Select(indices)) => {
+                self.clear_selection();
+                self.selection.extend(indices.iter().copied());
+                self.selected = indices.last().copied();
+                self.scroll_to_selected = true;
+            }
+            Some(Fix::Relink(i)) => {
+                let obj = &self.scene.objects[i];
+                if let Some(path) = &obj.image_path {
+                    self.relink.start(obj.id, path);
+                }
+            }
+            Some(Fix::Separate(indices)) => self.separate(&indices),
+            Some(Fix::BringBack(indices)) => self.bring_back(&indices),
+            None => {}
+        }
+
+        egui::Window::new("Preferences")
+            .open(&mut self.show_prefs)
+            .resizable(false)
+            .show(ctx, |ui| self.prefs.ui(ui));
+
+        egui::Window::new("Keyboard Shortcuts")
+            .open(&mut self.show_shortcuts)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(ui.ctx().screen_rect().height() * 0.7).show(ui, |ui| {
+                    self.prefs.keymap.reference_ui(ui);
+                });
+            });
+
+        let mut import = false;
+        let mut open = self.sheet_import.open;
+        egui::Window::new("Import Sprite Sheet")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| import = self.sheet_import.ui(ui));
+        self.sheet_import.open = open;
+        if import {
+            self.import_sheet(ctx);
+            self.sheet_import.open = false;
+        }
+
+        let mut relink = false;
+        let mut open = self.relink.open;
+        let shared = self.scene.objects.iter()
+            .filter(|o| o.id != self.relink.id && o.image_path.as_deref() == Some(self.relink.from.as_str()))
+            .count();
+        egui::Window::new("Relink Image")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| relink = self.relink.ui(ui, shared));
+        self.relink.open = open;
+        if relink {
+            self.apply_relink(ctx);
+            self.relink.open = false;
+        }
+
+        // 씬 뷰가 요청한 이미지 중 디코딩이 끝난 것을 받아 둠
+        let frame = ctx.frame_nr();
+        self.textures.max_bytes = self.prefs.texture_budget();
+        self.thumbs.max_bytes = Some(HIERARCHY_THUMB * HIERARCHY_THUMB * 4);
+        // 필터 설정이 바뀌면 텍스처를 내리고 화면에 보이는 것부터 다시 불러옴
+        if self.textures.options != self.prefs.texture_options() {
+            self.textures.options = self.prefs.texture_options();
+            self.image_cache.clear();
+            self.texture_used.clear();
+        }
+        for (path, tex) in self.textures.poll(ctx) {
+            match tex {
+                Some(tex) => {
+                    self.texture_used.insert(path.clone(), frame);
+                    self.image_cache.insert(path, tex);
+                }
+                None => {
+                    self.failed_images.insert(path);
+                }
+            }
+        }
+
+        for (path, tex) in self.thumbs.poll(ctx) {
+            match tex {
+                Some(tex) => {
+                    self.thumb_cache.insert(path, tex);
+                }
+                None => {
+                    self.failed_images.insert(path);
+                }
+            }
+        }
+
+        self.enforce_texture_budget(frame);
+
+        if let Some(load) = &self.scene_load {
+            let (fraction, text) = load.progress();
+            let mut cancel = false;
+            egui::Window::new("Opening Scene")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.label(&load.path);
+                    ui.add(egui::ProgressBar::new(fraction).text(text).animate(true));
+                    cancel = ui.button("Cancel").clicked();
+                });
+            if cancel {
+                load.cancel();
+                self.toasts.push(ctx, format!("Cancelled opening {}", load.path));
+                self.scene_load = None;
+            } else if let Some(result) = load.poll() {
+                let path = load.path.clone();
+                self.scene_load = None;
+                match result {
+                    Ok(scene) => {
+                        self.finish_open(&path, scene, ctx);
+                        self.texture_progress = Some((path, 0, frame));
+                    }
+                    // 편집 중인 씬은 그대로 둠
+                    Err(message) => self.load_error = Some((path, message)),
+                }
+            } else {
+                ctx.request_repaint();
+            }
+        } else if let Some((path, peak, opened)) = &mut self.texture_progress {
+            // 씬 뷰가 첫 프레임에 요청한 텍스처가 다 올라올 때까지. 나머지는 화면에 들어올 때 불러옴
+            let pending = self.textures.pending();
+            *peak = (*peak).max(pending);
+            let mut hide = false;
+            if pending > 0 {
+                let done = *peak - pending;
+                egui::Window::new("Opening Scene")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                    .show(ctx, |ui| {
+                        ui.label(path.as_str());
+                        let text = format!("Loading textures {} / {}", done, peak);
+                        ui.add(egui::ProgressBar::new(done as f32 / *peak as f32).text(text).animate(true));
+                        hide = ui.button("Continue in Background").clicked();
+                    });
+            }
+            if hide || (pending == 0 && frame > *opened + 1) {
+                self.texture_progress = None;
+            }
+        }
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let mut center = self.view_center();
+                let mut percent = self.zoom * 100.0;
+                ui.label("View:");
+                let x = ui.add(egui::DragValue::new(&mut center[0]).speed(0.1).prefix("x "));
+                let y = ui.add(egui::DragValue::new(&mut center[1]).speed(0.1).prefix("y "));
+                ui.separator();
+                ui.label("Zoom:");
+                let z = ui.add(egui::DragValue::new(&mut percent).speed(1.0).suffix("%").clamp_range(10.0..=500.0));
+                if x.changed() || y.changed() || z.changed() {
+                    self.set_view(center, percent / 100.0);
+                }
+                if ui.small_button("1:1").on_hover_text("Actual size (100%)").clicked() {
+                    self.run_action(Action::ActualSize, ctx);
+                }
+                if ui.small_button("Fit").on_hover_text("Frame all objects").clicked() {
+                    self.run_action(Action::FrameAll, ctx);
+                }
+                ui.separator();
+                self.bookmarks_menu(ui);
+                ui.separator();
+                // 화면 전체를 돌려 보기만 할 뿐 오브젝트 좌표는 바뀌지 않음
+                ui.label("Rotate:");
+                let mut degrees = self.view_rotation.to_degrees();
+                if ui.small_button("⟲").on_hover_text("Rotate view 15° counter-clockwise").clicked() {
+                    degrees = ((degrees / 15.0).round() - 1.0) * 15.0;
+                }
+                ui.add(egui::DragValue::new(&mut degrees).speed(1.0).max_decimals(1).suffix("°"));
+                if ui.small_button("⟳").on_hover_text("Rotate view 15° clockwise").clicked() {
+                    degrees = ((degrees / 15.0).round() + 1.0) * 15.0;
+                }
+                if ui.add_enabled(degrees != 0.0, egui::Button::new("0°").small()).on_hover_text("Reset view rotation").clicked() {
+                    degrees = 0.0;
+                }
+                self.view_rotation = (degrees.rem_euclid(360.0)).to_radians();
+                ui.separator();
+                ui.label(format!("{} object(s)", self.scene.objects.len()));
+                let pending = self.textures.pending();
+                if pending > 0 {
+                    ui.separator();
+                    ui.spinner();
+                    ui.label(format!("Loading {} image(s)…", pending));
+                }
+            });
+        });
+
+        let mut focus_requested = false;
+        egui::SidePanel::left("hierarchy").show_animated(ctx, self.prefs.show_hierarchy, |ui| {
             ui.heading("Hierarchy");
-            for (i, obj) in self.objects.iter().enumerate() {
-                if ui.selectable_label(self.selected == Some(i), &obj.name).clicked() {
-                    self.selected = Some(i);
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.hierarchy_query).hint_text("Search (tag:, prop:)").desired_width(140.0));
+                if !self.hierarchy_query.is_empty() && ui.small_button("✖").clicked() {
+                    self.hierarchy_query.clear();
+                }
+            });
+            let searching = !self.hierarchy_query.trim().is_empty();
+            egui::ScrollArea::vertical().max_height((ui.available_height() - 100.0).max(0.0)).show(ui, |ui| {
+                let mut reparent = None;
+                let mut clicked = None;
+                let mut toggle_collapse = None;
+                let parents: std::collections::HashSet<usize> = self.scene.objects.iter().filter_map(|o| o.parent).collect();
+                // 접힌 오브젝트의 자손은 건너뜀
+                let mut hidden_below = None;
+                for (i, depth) in self.scene.tree_order() {
+                    let obj = &self.scene.objects[i];
+                    // 검색 중에는 접힘과 상관없이 일치하는 오브젝트만 보여줌
+                    if searching {
+                        if !obj.matches_query(&self.hierarchy_query) {
+                            continue;
+                        }
+                    } else if hidden_below.is_some_and(|d| depth > d) {
+                        continue;
+                    }
+                    hidden_below = None;
+                    let collapsed = self.collapsed.contains(&obj.id);
+                    if collapsed {
+                        hidden_below = Some(depth);
+                    }
+                    let path = obj.image_path.as_deref().filter(|p| !p.is_empty());
+                    let drag = ui.dnd_drag_source(egui::Id::new(("hierarchy_row", obj.id)), obj.id, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 12.0);
+                            if parents.contains(&obj.id) {
+                                if ui.small_button(if collapsed { "▶" } else { "▼" }).clicked() {
+                                    toggle_collapse = Some(obj.id);
+                                }
+                            } else {
+                                ui.add_space(18.0);
+                            }
+                            let thumb = egui::vec2(16.0, 16.0);
+                            // 원본이 이미 올라와 있으면 그대로, 아니면 작은 텍스처를 백그라운드에서 만듦
+                            let loaded = path.and_then(|p| self.image_cache.get(p).or_else(|| self.thumb_cache.get(p)));
+                            match (path, loaded) {
+                                (_, Some(tex)) => {
+                                    ui.add(egui::Image::new((tex.id(), thumb)).uv(obj.uv()));
+                                }
+                                (Some(p), None) if self.failed_images.contains(p) => {
+                                    let warning = egui::Label::new(egui::RichText::new("⚠").color(egui::Color32::YELLOW))
+                                        .sense(egui::Sense::click());
+                                    if ui.add_sized(thumb, warning).clicked() {
+                                        self.relink.start(obj.id, p);
+                                    }
+                                }
+                                (Some(p), None) => {
+                                    self.thumbs.request(p);
+                                    ui.add_sized(thumb, egui::Spinner::new());
+                                }
+                                (None, None) => {
+                                    ui.add_sized(thumb, egui::Label::new("📄"));
+                                }
+                            }
+                            // 이미지 로드에 실패한 오브젝트는 이름도 경고 색으로
+                            let mut name = egui::RichText::new(&obj.name);
+                            if path.is_some_and(|p| self.failed_images.contains(p)) {
+                                name = name.color(egui::Color32::from_rgb(255, 0, 255));
+                            }
+                            if !obj.note.is_empty() {
+                                name = name.italics();
+                                ui.label("📝");
+                            }
+                            ui.selectable_label(self.selection.contains(&i), name)
+                        }).inner
+                    });
+
+                    // 드롭 대상 표시: 자기 자신이나 자손 위로는 놓을 수 없음
+                    if let Some(dragged) = drag.response.dnd_hover_payload::<usize>() {
+                        let valid = !self.scene.is_descendant(obj.id, *dragged);
+                        let color = if valid { egui::Color32::LIGHT_GREEN } else { egui::Color32::RED };
+                        ui.painter().rect_stroke(drag.response.rect, 2.0, egui::Stroke::new(1.0, color));
+                        if valid {
+                            if let Some(dragged) = drag.response.dnd_release_payload::<usize>() {
+                                reparent = Some((*dragged, Some(obj.id)));
+                            }
+                        }
+                    }
+
+                    let mut tooltip = match path {
+                        Some(p) => match self.image_cache.get(p) {
+                            Some(tex) => format!("{}\n{} x {} px", p, tex.size()[0], tex.size()[1]),
+                            None if self.failed_images.contains(p) => format!("{}\n(failed to load)", p),
+                            None => p.to_string(),
+                        },
+                        None => "No image".to_string(),
+                    };
+                    if !obj.note.is_empty() {
+                        tooltip = format!("{}\n\n📝 {}", tooltip, obj.note);
+                    }
+                    let row = drag.inner.on_hover_text(tooltip);
+                    if row.clicked() {
+                        clicked = Some((i, ui.input(|inp| inp.modifiers.command || inp.modifiers.shift)));
+                    }
+                    if row.double_clicked() {
+                        focus_requested = true;
+                    }
+                    if self.scroll_to_selected && self.selected == Some(i) {
+                        row.scroll_to_me(None);
+                        self.scroll_to_selected = false;
+                    }
+                }
+
+                // 빈 공간에 놓으면 루트로
+                let rest = ui.allocate_response(
+                    egui::vec2(ui.available_width(), ui.available_height().max(24.0)),
+                    egui::Sense::hover(),
+                );
+                if rest.dnd_hover_payload::<usize>().is_some() {
+                    ui.painter().rect_stroke(rest.rect, 2.0, egui::Stroke::new(1.0, egui::Color32::LIGHT_GREEN));
                 }
+                if let Some(dragged) = rest.dnd_release_payload::<usize>() {
+                    reparent = Some((*dragged, None));
+                }
+                if let Some((id, parent)) = reparent {
+                    self.scene.reparent(id, parent);
+                }
+                if let Some(id) = toggle_collapse {
+                    if !self.collapsed.remove(&id) {
+                        self.collapsed.insert(id);
+                    }
+                }
+                // 이미 선택된 행을 다시 누르면 위치를 다시 알려줌
+                if clicked.is_some_and(|(i, _)| self.selected == Some(i)) {
+                    self.pinged_selection = None;
+                }
+                match clicked {
+                    Some((i, true)) => self.toggle_select(i),
+                    Some((i, false)) => self.select(i),
+                    None => {}
+                }
+            });
+            if focus_requested {
+                self.focus_selected();
             }
             if ui.button("Add Object").clicked() {
-                let id = self.objects.len();
-                self.objects.push(GameObject {
-                    id,
-                    name: format!("Object {}", id),
-                    position: [0.0, 0.0],
-                    rotation: 0.0,
-                    scale: [1.0, 1.0],
-                    image_path: None,
-                });
+                self.run_action(Action::AddObject, ctx);
             }
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Duplicate")).clicked() {
+                    self.run_action(Action::Duplicate, ctx);
+                }
+                if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("In Place")).on_hover_text("Duplicate without offset").clicked() {
+                    self.run_action(Action::DuplicateInPlace, ctx);
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Group")).clicked() {
+                    self.run_action(Action::Group, ctx);
+                }
+                if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Ungroup")).clicked() {
+                    self.run_action(Action::Ungroup, ctx);
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!self.selection.is_empty(), egui::Button::new("Export Prefab...")).clicked() {
+                    self.run_action(Action::ExportPrefab, ctx);
+                }
+                if ui.button("Insert Prefab...").clicked() {
+                    self.run_action(Action::InsertPrefab, ctx);
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Import Sprite Sheet...").clicked() {
+                    self.run_action(Action::ImportSheet, ctx);
+                }
+                if ui.button("Import CSV...").on_hover_text("name,x,y,rotation,scale_x,scale_y,image_path").clicked() {
+                    self.run_action(Action::ImportCsv, ctx);
+                }
+            });
             ui.separator();
+            ui.label(format!("File: {}{}", self.current_path, if self.dirty { " *" } else { "" }));
             if ui.button("💾 Save Scene").clicked() {
-                save::save_scene(&self.objects, "scene.ron");
+                self.run_action(Action::Save, ctx);
             }
+            ui.horizontal(|ui| {
+                if ui.button("Save As...").clicked() {
+                    self.run_action(Action::SaveAs, ctx);
+                }
+                if ui.button("Save a Copy As...").clicked() {
+                    self.run_action(Action::SaveCopy, ctx);
+                }
+            });
             if ui.button("📂 Load Scene").clicked() {
-                self.objects = save::load_scene("scene.ron");
+                let path = self.current_path.clone();
+                self.open(&path);
+            }
+            if ui.button("Open...").clicked() {
+                self.run_action(Action::Open, ctx);
             }
         });
 
-        egui::SidePanel::right("inspector").show(ctx, |ui| {
+        let inspector = egui::SidePanel::right("inspector").show_animated(ctx, self.prefs.show_inspector, |ui| {
             ui.heading("Inspector");
             if let Some(i) = self.selected {
-                let path = self.objects[i].image_path.clone().unwrap_or_default();
+                let path = self.scene.objects[i].image_path.clone();
+                if let Some(path) = path.as_ref().filter(|p| !self.image_cache.contains_key(*p) && !self.failed_images.contains(*p)) {
+                    self.textures.request(path);
+                }
+
+                let worlds = self.scene.world_transforms();
+                let world = worlds[i];
+                let parent_world = self.scene.parent_world(&worlds, i);
+                let anchor_world = self.scene.anchor_world(&worlds, i);
+                let references: Vec<(usize, String, [f32; 2])> = self.scene.objects.iter().zip(&worlds)
+                    .filter(|(o, _)| o.id != self.scene.objects[i].id)
+                    .map(|(o, w)| (o.id, o.name.clone(), w.position))
+                    .collect();
+                // 순환이 생기지 않는 기준 후보만
+                let anchor_candidates: Vec<(usize, String)> = references.iter()
+                    .filter(|(id, _, _)| !self.scene.depends_on(*id, self.scene.objects[i].id))
+                    .map(|(id, name, _)| (*id, name.clone()))
+                    .collect();
+                let mut set_anchor = None;
+                let parent = self.scene.objects[i].parent
+                    .and_then(|p| self.scene.index_of(p))
+                    .map(|p| self.scene.objects[p].name.clone());
+                let mut unparent = false;
+                let mut match_rotation = false;
+                let mut match_scale = false;
+                let mut set_rotation: Option<(f32, bool)> = None;
+                let mut move_by = None;
+                let mut reveal_failed = None;
+                let mut set_image = None;
+                let mut name_after_image = false;
+                let mut browse_image = false;
+                let count = self.scene.objects.len();
 
-                if !self.image_cache.contains_key(&path) {
-                    if let Some(tex) = StarEditor::load_image(&path, ctx) {
-                        self.image_cache.insert(path.clone(), tex);
+                // 적용 모드: (원본, 사본)을 두고 사본을 편집하다가 Apply/Enter 때 한 번에 반영
+                if self.prefs.staged_edits {
+                    let current = &self.scene.objects[i];
+                    // 씬 뷰 등에서 원본이 바뀌면 편집한 필드만 얹어서 새 원본 기준으로 다시 잡음
+                    if let Some((base, draft)) = self.staged.as_mut().filter(|(base, _)| base.id == current.id && base != current) {
+                        let mut rebased = current.clone();
+                        rebased.apply_changes(base, draft);
+                        *draft = rebased;
+                        *base = current.clone();
+                    }
+                    let stale = self.staged.as_ref()
+                        .is_none_or(|(base, draft)| base.id != current.id || base == draft);
+                    if stale {
+                        self.staged = Some((current.clone(), current.clone()));
                     }
+                } else {
+                    self.staged = None;
                 }
+                let obj = match &mut self.staged {
+                    Some((_, draft)) => draft,
+                    None => &mut self.scene.objects[i],
+                };
 
-                let obj = &mut self.objects[i];
-                obj.image_path = Some(path.clone());
-
+                if self.selection.len() > 1 {
+                    ui.label(format!("{} objects selected", self.selection.len()));
+                    ui.horizontal(|ui| {
+                        match_rotation = ui.button("Match Rotation").clicked();
+                        match_scale = ui.button("Match Scale").clicked();
+                    });
+                    ui.separator();
+                }
                 ui.label(format!("ID: {}", obj.id));
-                ui.text_edit_singleline(&mut obj.name);
                 ui.horizontal(|ui| {
-                    ui.label("Position:");
-                    ui.add(egui::DragValue::new(&mut obj.position[0]));
-                    ui.add(egui::DragValue::new(&mut obj.position[1]));
+                    ui.label(format!("Draw order: {} / {}", i, count - 1));
+                    move_by = if ui.add_enabled(i > 0, egui::Button::new("Move Down").small()).clicked() {
+                        Some(i - 1)
+                    } else if ui.add_enabled(i + 1 < count, egui::Button::new("Move Up").small()).clicked() {
+                        Some(i + 1)
+                    } else {
+                        None
+                    };
+                });
+                if let Some(parent) = parent {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Parent: {}", parent));
+                        unparent = ui.small_button("Unparent").clicked();
+                    });
+                }
+                let name_edit = ui.text_edit_singleline(&mut obj.name);
+                if self.focus_name {
+                    name_edit.request_focus();
+                    self.focus_name = false;
+                }
+                ui.horizontal(|ui| {
+                    let anchor_name = |id: Option<usize>| {
+                        match id.and_then(|id| references.iter().find(|r| r.0 == id)) {
+                            Some((id, name, _)) => format!("#{} {}", id, name),
+                            None => "(none)".to_string(),
+                        }
+                    };
+                    ui.label("Anchor:");
+                    egui::ComboBox::from_id_source(("anchor_to", obj.id))
+                        .selected_text(anchor_name(obj.anchor_to))
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(obj.anchor_to.is_none(), "(none)").clicked() {
+                                set_anchor = Some(None);
+                            }
+                            for (id, name) in &anchor_candidates {
+                                if ui.selectable_label(obj.anchor_to == Some(*id), format!("#{} {}", id, name)).clicked() {
+                                    set_anchor = Some(Some(*id));
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text("Keep a fixed offset from another object's position");
+                });
+                ui.horizontal(|ui| {
+                    // 고정된 오브젝트는 위치 대신 기준으로부터의 오프셋을 편집
+                    if obj.anchor_to.is_some() {
+                        ui.label("Offset:");
+                        ui.add(egui::DragValue::new(&mut obj.anchor_offset[0]));
+                        ui.add(egui::DragValue::new(&mut obj.anchor_offset[1]));
+                    } else {
+                        ui.label("Position:");
+                        ui.add(egui::DragValue::new(&mut obj.position[0]));
+                        ui.add(egui::DragValue::new(&mut obj.position[1]));
+                    }
+                    ui.checkbox(&mut obj.snap, "Snap").on_hover_text("Follow grid and edge snapping while dragging");
+                });
+                ui.collapsing("Relative Position", |ui| {
+                    // 기준 오브젝트 위치 + 오프셋으로 한 번만 계산해 넣음 (기준이 움직여도 따라가지 않음)
+                    let reference = references.iter().find(|r| Some(r.0) == self.relative_to);
+                    egui::ComboBox::from_id_source(("relative_to", obj.id))
+                        .selected_text(reference.map_or("(pick an object)".to_string(), |r| format!("#{} {}", r.0, r.1)))
+                        .show_ui(ui, |ui| {
+                            for (id, name, _) in &references {
+                                ui.selectable_value(&mut self.relative_to, Some(*id), format!("#{} {}", id, name));
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        ui.label("Offset:");
+                        ui.add(egui::DragValue::new(&mut self.relative_offset[0]).speed(0.1));
+                        ui.add(egui::DragValue::new(&mut self.relative_offset[1]).speed(0.1));
+                    });
+                    ui.horizontal(|ui| {
+                        let Some(&(id, _, base)) = reference else {
+                            return;
+                        };
+                        if ui.button("Place").on_hover_text("Move this object to the reference position plus the offset").clicked() {
+                            let target = [base[0] + self.relative_offset[0], base[1] + self.relative_offset[1]];
+                            obj.set_world_position(parent_world, anchor_world, Transform { position: target, ..world });
+                            self.edit_label = Some(format!("Place relative to #{}", id));
+                        }
+                        if ui.small_button("From Current").on_hover_text("Fill in the offset from where this object is now").clicked() {
+                            self.relative_offset = [world.position[0] - base[0], world.position[1] - base[1]];
+                        }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Rotation:");
+                    ui.add(egui::DragValue::new(&mut obj.rotation));
+                });
+                ui.horizontal(|ui| {
+                    for deg in [0.0, 90.0, 180.0, 270.0] {
+                        if ui.small_button(format!("{}°", deg)).clicked() {
+                            set_rotation = Some((deg, false));
+                        }
+                    }
+                    ui.separator();
+                    for deg in [-90.0, -45.0, 45.0, 90.0] {
+                        if ui.small_button(format!("{:+}°", deg)).clicked() {
+                            set_rotation = Some((deg, true));
+                        }
+                    }
                 });
+                let mut mirror = None;
                 ui.horizontal(|ui| {
-                    ui.label("Rotation:");
-                    ui.add(egui::DragValue::new(&mut obj.rotation));
+                    if ui.small_button("Mirror H").clicked() {
+                        mirror = Some(Action::MirrorHorizontal);
+                    }
+                    if ui.small_button("Mirror V").clicked() {
+                        mirror = Some(Action::MirrorVertical);
+                    }
+                    ui.checkbox(&mut self.settings.mirror_about_origin, "about origin");
                 });
                 ui.horizontal(|ui| {
                     ui.label("Scale:");
-                    ui.add(egui::DragValue::new(&mut obj.scale[0]));
-                    ui.add(egui::DragValue::new(&mut obj.scale[1]));
+                    // 음수 스케일을 허용하지 않으면 뒤집기는 Flip X/Y로만
+                    let range = if self.prefs.negative_scale { -1000.0..=1000.0 } else { 0.01..=1000.0 };
+                    ui.add(egui::DragValue::new(&mut obj.scale[0]).clamp_range(range.clone()));
+                    ui.add(egui::DragValue::new(&mut obj.scale[1]).clamp_range(range));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Pivot:");
+                    ui.add(egui::DragValue::new(&mut obj.pivot[0]).speed(0.01).clamp_range(PIVOT_RANGE));
+                    ui.add(egui::DragValue::new(&mut obj.pivot[1]).speed(0.01).clamp_range(PIVOT_RANGE));
+                    if ui.small_button("Center").clicked() {
+                        obj.pivot = default_pivot();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Image:");
+                    if ui.selectable_label(self.editing_image_path, "✏").on_hover_text("Edit path").clicked() {
+                        self.editing_image_path = !self.editing_image_path;
+                    }
+                    browse_image = ui.small_button("Browse...").on_hover_text("Set the image of every selected object").clicked();
+                    if let Some(path) = obj.image_path.as_ref().filter(|p| !self.editing_image_path && !p.is_empty()) {
+                        if ui.small_button("Reveal").on_hover_text("Show the file in the file manager").clicked() && !reveal_in_file_manager(path) {
+                            reveal_failed = Some(path.clone());
+                        }
+                    }
+                    if let Some(path) = obj.image_path.as_ref().filter(|p| self.failed_images.contains(*p)) {
+                        if ui.button("Relink...").clicked() {
+                            self.relink.start(obj.id, path);
+                        }
+                    }
+                });
+                if self.editing_image_path {
+                    let mut text = obj.image_path.clone().unwrap_or_default();
+                    let edit = ui.add(egui::TextEdit::singleline(&mut text).desired_width(f32::INFINITY));
+                    if edit.changed() {
+                        obj.set_image_path(Some(text));
+                        set_image = Some(obj.image_path.clone());
+                    }
+                    // 입력 중간의 경로로 이름이 바뀌지 않게 입력을 마쳤을 때만
+                    if edit.lost_focus() && self.prefs.auto_name {
+                        obj.name_after_image();
+                        name_after_image = true;
+                    }
+                    if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.editing_image_path = false;
+                    }
+                } else {
+                    // 긴 경로는 줄바꿈해서 보여주고 선택해 복사할 수 있게 (읽기 전용)
+                    let mut text = obj.image_path.as_deref().unwrap_or("(none)");
+                    let full = obj.image_path.as_ref()
+                        .map(|p| std::fs::canonicalize(p).map_or(p.clone(), |abs| abs.display().to_string()))
+                        .unwrap_or_else(|| "No image".to_string());
+                    ui.add(egui::TextEdit::multiline(&mut text).desired_rows(1).desired_width(f32::INFINITY))
+                        .on_hover_text(full);
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Tint:");
+                    ui.color_edit_button_srgba_unmultiplied(&mut obj.tint);
+                    ui.checkbox(&mut obj.flip_x, "Flip X");
+                    ui.checkbox(&mut obj.flip_y, "Flip Y");
                 });
+                let layer_name = |id: Option<usize>| {
+                    id.and_then(|id| self.scene.layers.iter().find(|l| l.id == id))
+                        .map_or("(none)".to_string(), |l| l.name.clone())
+                };
+                egui::ComboBox::from_label("Layer")
+                    .selected_text(layer_name(obj.layer))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut obj.layer, None, "(none)");
+                        for layer in &self.scene.layers {
+                            ui.selectable_value(&mut obj.layer, Some(layer.id), &layer.name);
+                        }
+                    });
+                let mut use_uv = obj.uv_rect.is_some();
+                if ui.checkbox(&mut use_uv, "UV Sub-rect").changed() {
+                    obj.uv_rect = use_uv.then_some([0.0, 0.0, 1.0, 1.0]);
+                }
+                if let Some(uv) = &mut obj.uv_rect {
+                    ui.horizontal(|ui| {
+                        ui.label("Min:");
+                        ui.add(egui::DragValue::new(&mut uv[0]).speed(0.01).clamp_range(0.0..=1.0));
+                        ui.add(egui::DragValue::new(&mut uv[1]).speed(0.01).clamp_range(0.0..=1.0));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Max:");
+                        ui.add(egui::DragValue::new(&mut uv[2]).speed(0.01).clamp_range(0.0..=1.0));
+                        ui.add(egui::DragValue::new(&mut uv[3]).speed(0.01).clamp_range(0.0..=1.0));
+                    });
+                }
+                ui.label("Note:");
+                ui.add(egui::TextEdit::multiline(&mut obj.note).desired_rows(2).hint_text("Notes for collaborators"));
+                ui.label("Tags:");
+                widgets::tags_editor(ui, ("tags", obj.id), &mut obj.tags);
+                ui.label("Properties:");
+                widgets::properties_editor(ui, ("properties", obj.id), &mut obj.properties);
+
+                ui.separator();
+                let bounds = world.centered(obj.pivot).aabb();
+                ui.label(format!("Size: {:.2} x {:.2} units", world.size()[0].abs(), world.size()[1].abs()));
+                ui.label(format!("Bounds: {:.2} x {:.2} units", bounds.width(), bounds.height()));
+                if let Some(tex) = path.as_ref().and_then(|p| self.image_cache.get(p)) {
+                    let [w, h] = tex.size();
+                    let kind = if path.as_ref().is_some_and(|p| self.textures.svg_size(p).is_some()) { ", SVG rasterized" } else { "" };
+                    ui.label(format!("Image: {} x {} px (aspect {:.2}{})", w, h, w as f32 / h as f32, kind));
+                }
+                if let Some((base, draft)) = &self.staged {
+                    let pending = base != draft;
+                    ui.separator();
+                    let (mut apply, mut revert) = (false, false);
+                    ui.horizontal(|ui| {
+                        apply = ui.add_enabled(pending, egui::Button::new("Apply")).clicked();
+                        revert = ui.add_enabled(pending, egui::Button::new("Revert")).clicked();
+                        if pending {
+                            ui.label("Unapplied changes");
+                        }
+                    });
+                    // Enter로 인스펙터 입력칸을 마쳤을 때만 (여러 줄 메모는 Enter에 포커스를 잃지 않음)
+                    apply |= pending && inspector_enter && ui.memory(|m| m.focused()) != focus_at_start;
+                    if apply {
+                        if let Some((base, draft)) = self.staged.take() {
+                            self.scene.objects[i].apply_changes(&base, &draft);
+                        }
+                    } else if revert {
+                        self.staged = None;
+                    }
+                }
+                if unparent {
+                    let id = self.scene.objects[i].id;
+                    self.scene.reparent(id, None);
+                }
+                if let Some(anchor) = set_anchor {
+                    let id = self.scene.objects[i].id;
+                    self.edit_label = Some(match anchor {
+                        Some(a) => format!("Anchor to #{}", a),
+                        None => "Remove anchor".to_string(),
+                    });
+                    self.scene.set_anchor(id, anchor);
+                }
+                // 여러 개 선택했으면 같은 이미지를 모두에게
+                if let Some(path) = set_image.filter(|_| self.selection.len() > 1) {
+                    self.set_selection_image(path, false);
+                }
+                if name_after_image && self.selection.len() > 1 {
+                    for &j in &self.selection {
+                        self.scene.objects[j].name_after_image();
+                    }
+                }
+                if browse_image {
+                    let current = self.scene.objects[i].image_path.clone().unwrap_or_default();
+                    self.path_prompt.open(PathAction::SetImage, &current);
+                }
+                if let Some(path) = reveal_failed {
+                    self.toasts.push(ctx, format!("Could not open the folder of {}", path));
+                }
+                if match_rotation || match_scale {
+                    self.match_transform(match_rotation, match_scale);
+                }
+                if let Some(j) = move_by {
+                    self.swap_objects(i, j);
+                }
+                if let Some(action) = mirror {
+                    self.run_action(action, ctx);
+                }
+                if let Some((deg, relative)) = set_rotation {
+                    for &j in &self.selection {
+                        let obj = &mut self.scene.objects[j];
+                        obj.rotation = if relative { obj.rotation + deg.to_radians() } else { deg.to_radians() };
+                    }
+                }
             } else {
                 ui.label("No object selected.");
             }
         });
+        self.inspector_rect = inspector.map_or(egui::Rect::NOTHING, |r| r.response.rect);
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            // zoom 조절
-            let zoom_delta = ctx.input(|i| {
-                i.events.iter().filter_map(|e| match e {
-                    egui::Event::Scroll(delta) => Some(delta.y),
-                    _ => None,
-                }).sum::<f32>()
-            });
-            if zoom_delta != 0.0 {
-                self.zoom += zoom_delta * 0.01;
-                self.zoom = self.zoom.clamp(0.1, 5.0);
-            }
-
+            let ppu = self.scene.pixels_per_unit;
             ui.heading("Scene View");
+            // 지워지거나 되돌리기로 사라진 그룹에서는 빠져나옴
+            self.entered_group = self.entered_group.filter(|&g| self.scene.index_of(g).is_some());
+            if let Some(group) = self.entered_group {
+                ui.horizontal(|ui| {
+                    let mut path = vec![group];
+                    while let Some(parent) = path.last()
+                        .and_then(|&id| self.scene.index_of(id))
+                        .and_then(|i| self.scene.objects[i].parent)
+                        .filter(|p| self.scene.index_of(*p).is_some() && !path.contains(p))
+                    {
+                        path.push(parent);
+                    }
+                    if ui.small_button("Scene").clicked() {
+                        self.entered_group = None;
+                    }
+                    for &id in path.iter().rev() {
+                        ui.label("›");
+                        let name = &self.scene.objects[self.scene.index_of(id).unwrap_or_default()].name;
+                        if ui.selectable_label(id == group, name).clicked() {
+                            self.entered_group = Some(id);
+                        }
+                    }
+                    ui.weak("(Esc to exit)");
+                });
+            }
 
             // 영역 확보 및 상호작용 등록
             let available_size = ui.available_size();
             let (rect, response) = ui.allocate_exact_size(available_size, egui::Sense::click_and_drag());
             let painter = ui.painter_at(rect);
+            self.scene_rect = rect;
+
+            // zoom 조절 (씬 뷰 위에서 스크롤할 때만)
+            let zoom_delta = if response.hovered() {
+                ctx.input(|i| {
+                    i.events.iter().filter_map(|e| match e {
+                        egui::Event::Scroll(delta) => Some(delta.y),
+                        _ => None,
+                    }).sum::<f32>()
+                })
+            } else {
+                0.0
+            };
+            let direction = if self.prefs.invert_zoom { -1.0 } else { 1.0 };
+            let (zoom_in, zoom_out) = ctx.input(|i| {
+                (
+                    keyboard_free && keymap.pressed(i, Action::ZoomIn),
+                    keyboard_free && keymap.pressed(i, Action::ZoomOut),
+                )
+            });
+            if self.settings.pixel_zoom {
+                let steps = pixel_zoom_steps(&mut self.pixel_zoom_scroll, zoom_delta * direction)
+                    + zoom_in as i32
+                    - zoom_out as i32;
+                for _ in 0..steps.unsigned_abs() {
+                    self.zoom = step_pixel_zoom(self.zoom, steps > 0);
+                }
+            } else {
+                if zoom_delta != 0.0 {
+                    self.zoom += zoom_delta * self.prefs.zoom_speed * direction;
+                }
+                if zoom_in {
+                    self.zoom *= 1.1;
+                }
+                if zoom_out {
+                    self.zoom /= 1.1;
+                }
+                self.zoom = self.zoom.clamp(0.1, 5.0);
+            }
 
-            painter.rect_filled(rect, 0.0, egui::Color32::from_gray(30));
+            let [r, g, b, a] = self.scene.background_color;
+            painter.rect_filled(rect, 0.0, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
 
             let pointer_pos = response.interact_pointer_pos();
 
-            for (i, obj) in self.objects.iter_mut().enumerate() {
-                let center = egui::pos2(
-                    rect.left_top().x + self.view_offset[0] + obj.position[0] * 10.0 * self.zoom,
-                    rect.left_top().y + self.view_offset[1] + obj.position[1] * 10.0 * self.zoom,
+            // 배치 모드: 클릭하면 놓고 Esc로 취소
+            // 배치 중이 아니면 들어간 그룹에서 한 단계 나옴
+            if keyboard_free && ctx.input(|i| keymap.pressed(i, Action::Cancel)) {
+                if self.placing.is_some() {
+                    self.placing = None;
+                } else if let Some(group) = self.entered_group {
+                    self.entered_group = self.scene.index_of(group).and_then(|i| self.scene.objects[i].parent);
+                }
+            }
+            // 프리팹을 넣을 위치로 쓰려고 마지막 커서 위치를 기억
+            let view = View::new(rect, self.view_offset, ppu * self.zoom, self.view_rotation);
+            if let Some(hover) = response.hover_pos() {
+                self.scene_cursor = Some(view.to_world(hover));
+            }
+            let snap_step = self.snap_step(ctx.input(|i| i.modifiers.shift));
+            if let (Some(ghost), Some(hover)) = (self.placing.as_mut(), response.hover_pos()) {
+                let mut world = view.to_world(hover);
+                if self.settings.snap_to_grid && !ctx.input(|i| i.modifiers.alt) {
+                    world = world.map(|v| (v / snap_step).round() * snap_step);
+                }
+                ghost.position = world;
+                let size = egui::vec2(ghost.scale[0], ghost.scale[1]) * OBJECT_SIZE;
+                view.outline(
+                    &painter,
+                    egui::Rect::from_center_size(egui::pos2(world[0], world[1]), size),
+                    egui::Stroke::new(1.5, egui::Color32::from_white_alpha(140)),
                 );
+                if response.clicked() {
+                    if let Some(mut obj) = self.placing.take() {
+                        obj.id = self.next_id;
+                        obj.name = format!("Object {}", obj.id);
+                        self.next_id += 1;
+                        self.scene.objects.push(obj);
+                        self.select(self.scene.objects.len() - 1);
+                    }
+                }
+            }
+
+            // 카메라 이동
+            if ctx.input(|i| i.pointer.secondary_down()) {
+                if let Some(current) = response.interact_pointer_pos() {
+                    if let Some(start) = self.pan_start {
+                        let delta = view.rotation.inverse() * (current - start);
+                        self.view_offset[0] += delta.x;
+                        self.view_offset[1] += delta.y;
+                        self.pan_start = Some(current);
+                    } else {
+                        self.pan_start = Some(current);
+                    }
+                }
+            } else {
+                self.pan_start = None;
+            }
+
+            ctx.input(|i| {
+                if !keyboard_free {
+                    return;
+                }
+                // 회전한 화면 기준의 위/아래/왼쪽/오른쪽으로 이동
+                let mut step = egui::Vec2::ZERO;
+                if keymap.down(i, Action::PanUp) {
+                    step.y += 1.0;
+                }
+                if keymap.down(i, Action::PanDown) {
+                    step.y -= 1.0;
+                }
+                if keymap.down(i, Action::PanLeft) {
+                    step.x += 1.0;
+                }
+                if keymap.down(i, Action::PanRight) {
+                    step.x -= 1.0;
+                }
+                let step = view.rotation.inverse() * step * self.prefs.pan_speed;
+                self.view_offset[0] += step.x;
+                self.view_offset[1] += step.y;
+            });
+
+            // 씬 경계가 화면 밖으로 완전히 나가지 않게 (최소 KEEP px는 남김)
+            let scene_bounds = self.scene.bounds();
+            let screen_bounds = |offset: [f32; 2], b: egui::Rect| {
+                View::new(rect, offset, ppu * self.zoom, self.view_rotation).screen_bounds(b)
+            };
+            if let Some(b) = scene_bounds.filter(|_| self.settings.limit_pan) {
+                const KEEP: f32 = 40.0;
+                let screen = screen_bounds(self.view_offset, b);
+                self.view_offset[0] += (rect.left() + KEEP - screen.right()).max(0.0)
+                    - (screen.left() - (rect.right() - KEEP)).max(0.0);
+                self.view_offset[1] += (rect.top() + KEEP - screen.bottom()).max(0.0)
+                    - (screen.top() - (rect.bottom() - KEEP)).max(0.0);
+            }
+            let lost = scene_bounds.filter(|b| !screen_bounds(self.view_offset, *b).intersects(rect));
+
+            // 이동이 끝난 뒤 그려야 오브젝트와 한 프레임 어긋나지 않음
+            let view = View::new(rect, self.view_offset, ppu * self.zoom, self.view_rotation);
+            if self.settings.show_grid {
+                draw_grid(&painter, rect, view, self.settings.grid_size, self.settings.grid_subdivisions);
+            }
+
+            let worlds = self.scene.world_transforms();
+            let parent_worlds: Vec<Transform> =
+                (0..worlds.len()).map(|i| self.scene.parent_world(&worlds, i)).collect();
+            let anchor_worlds: Vec<Option<[f32; 2]>> =
+                (0..worlds.len()).map(|i| self.scene.anchor_world(&worlds, i)).collect();
+            let visible: Vec<bool> = self.scene.objects.iter()
+                .map(|o| self.scene.is_visible(o) && self.solo.as_ref().is_none_or(|ids| ids.contains(&o.id)))
+                .collect();
+            // 들어간 그룹 밖의 오브젝트는 잠긴 것처럼 다룸
+            let inside: Vec<bool> = self.scene.objects.iter()
+                .map(|o| self.entered_group.is_none_or(|g| o.id != g && self.scene.is_descendant(o.id, g)))
+                .collect();
+            let locked: Vec<bool> = self.scene.objects.iter().zip(&inside).map(|(o, &inside)| self.scene.is_locked(o) || !inside).collect();
+
+            let bodies: Vec<Transform> = worlds.iter().zip(&self.scene.objects).map(|(w, o)| w.centered(o.pivot)).collect();
+
+            let press_origin = ctx.input(|i| i.pointer.press_origin()).or(pointer_pos);
+            let unit = ppu * self.zoom;
+            let world_at = |pos: egui::Pos2| view.to_world(pos);
+            let pivot_screen = |i: usize| view.to_screen(worlds[i].position);
+
+            // 선택된 오브젝트의 피벗 핸들 드래그 (오브젝트 이동보다 우선)
+            if response.drag_started() && self.placing.is_none() {
+                if let (Some(i), Some(pos)) = (self.selected.filter(|&i| visible[i] && !locked[i]), press_origin) {
+                    if (pos - pivot_screen(i)).length() <= HANDLE_RADIUS {
+                        self.pivot_drag = Some(i);
+                    }
+                }
+            }
+            if let Some(i) = self.pivot_drag {
+                if let Some(pos) = pointer_pos {
+                    // 사각형은 제자리에 두고 피벗과 위치만 옮김
+                    let body = bodies[i];
+                    let pivot = body.pivot_at(world_at(pos)).map(|v| v.clamp(*PIVOT_RANGE.start(), *PIVOT_RANGE.end()));
+                    // 중심에서 반대 방향으로 옮기면 새 피벗의 월드 위치
+                    let point = body.centered([1.0 - pivot[0], 1.0 - pivot[1]]).position;
+                    let obj = &mut self.scene.objects[i];
+                    obj.pivot = pivot;
+                    obj.set_world_position(parent_worlds[i], anchor_worlds[i], Transform { position: point, ..worlds[i] });
+                }
+                if response.drag_stopped() {
+                    self.pivot_drag = None;
+                }
+            }
+            // 여러 개 선택했을 때: 묶음 상자 오른쪽 아래는 크기, 위쪽 가운데는 회전 핸들
+            let group_box = (self.selection.len() > 1)
+                .then(|| self.selection.iter().filter(|&&i| i < bodies.len()).map(|&i| bodies[i].aabb()).reduce(|a, b| a.union(b)))
+                .flatten();
+            let group_handles = group_box.map(|b| {
+                let top = view.to_screen([b.center().x, b.top()]);
+                // 많이 축소해서 상자가 점처럼 되어도 회전 핸들은 화면 위쪽으로
+                let up = top - view.to_screen([b.center().x, b.center().y]);
+                let up = if up.length() > 0.5 { up.normalized() } else { view.rotation * egui::vec2(0.0, -1.0) };
+                (view.to_screen([b.right(), b.bottom()]), top + up * ROTATE_HANDLE_OFFSET)
+            });
+            if response.drag_started() && self.placing.is_none() && self.pivot_drag.is_none() {
+                if let (Some((scale_at, rotate_at)), Some(pos), Some(b)) = (group_handles, press_origin, group_box) {
+                    let near = |at: egui::Pos2| (pos - at).length() <= HANDLE_RADIUS;
+                    let handle = if near(scale_at) {
+                        Some(GroupHandle::Scale)
+                    } else if near(rotate_at) {
+                        Some(GroupHandle::Rotate)
+                    } else {
+                        None
+                    };
+                    if let Some(handle) = handle {
+                        self.group_drag = Some((handle, [b.center().x, b.center().y], pos));
+                    }
+                }
+            }
+            if let Some((handle, pivot, last)) = self.group_drag {
+                if let Some(pos) = pointer_pos {
+                    let center = view.to_screen(pivot);
+                    let (from, to) = (last - center, pos - center);
+                    let around = |t: Transform, factor: f32, angle: f32| {
+                        let (sin, cos) = angle.sin_cos();
+                        let d = [(t.position[0] - pivot[0]) * factor, (t.position[1] - pivot[1]) * factor];
+                        [pivot[0] + d[0] * cos - d[1] * sin, pivot[1] + d[0] * sin + d[1] * cos]
+                    };
+                    match handle {
+                        GroupHandle::Scale if from.length() > 1.0 => {
+                            let factor = to.length() / from.length();
+                            self.transform_selection(|t| Transform {
+                                position: around(t, factor, 0.0),
+                                scale: t.scale.map(|v| v * factor),
+                                ..t
+                            });
+                        }
+                        GroupHandle::Rotate if from.length() > 1.0 && to.length() > 1.0 => {
+                            let angle = to.angle() - from.angle();
+                            self.transform_selection(|t| Transform {
+                                position: around(t, 1.0, angle),
+                                rotation: t.rotation + angle,
+                                ..t
+                            });
+                        }
+                        _ => {}
+                    }
+                    self.group_drag = Some((handle, pivot, pos));
+                }
+                if response.drag_stopped() {
+                    self.group_drag = None;
+                }
+            }
 
-                let size_x = obj.scale[0] * 20.0 * self.zoom;
-                let size_y = obj.scale[1] * 20.0 * self.zoom;
+            let mut clicked_object = None;
+            let mut group_move = None;
+            let mut edge_guides = Vec::new();
+            let mut duplicate_drag = false;
+            let mut overlay_ids = Vec::new();
+            let mut notes = Vec::new();
+            let mut shimmering = false;
+            // SVG 경로마다 화면에서 필요한 래스터 긴 변 (같은 이미지를 쓰는 오브젝트 중 가장 큰 것)
+            let mut svg_wanted: std::collections::HashMap<String, f32> = std::collections::HashMap::new();
+            let mut hovered_note = None;
+            let mut centers = Vec::new();
 
-                let bounding = egui::Rect::from_center_size(center, egui::vec2(size_x, size_y));
+            for (i, obj) in self.scene.objects.iter_mut().enumerate() {
+                if !visible[i] {
+                    continue;
+                }
+                let world = worlds[i];
+                let body = bodies[i];
+                let center = view.to_screen(body.position);
+                let quad = view.object_quad(body);
+                let bounding = egui::Rect::from_points(&quad);
+                // 그린 모양과 같은 변환으로 판정 (회전한 오브젝트나 화면도 맞음)
+                let contains = |pos: egui::Pos2| body.contains(view.to_world(pos));
+
+                // 클릭으로 선택 (위에 그려진 오브젝트가 우선)
+                let hit = press_origin.is_some_and(contains);
+                if response.clicked() && hit && !locked[i] && self.placing.is_none() {
+                    clicked_object = Some(i);
+                }
 
                 // 클릭 시작
-                if response.drag_started() {
-                    if let Some(pos) = pointer_pos {
-                        if bounding.contains(pos) {
+                if response.drag_started()
+                    && !locked[i]
+                    && (!self.selection_locked || self.selection.contains(&i))
+                    && self.placing.is_none()
+                    && self.pivot_drag.is_none()
+                    && self.group_drag.is_none()
+                {
+                    if let Some(pos) = press_origin {
+                        if contains(pos) {
                             self.dragging = Some(i);
                             self.drag_start = Some(pos);
-                            self.selected = Some(i);
+                            self.drag_moved = false;
+                            self.drag_raw = world.position;
+                            // Alt는 스냅 무시에 쓰므로 복제는 Ctrl. 선택 추가는 Ctrl+클릭으로만
+                            duplicate_drag = ctx.input(|inp| inp.modifiers.command);
+                            // 잠긴 선택은 그대로 두고 드래그만
+                            if !self.selection_locked {
+                                self.selected = Some(i);
+                                if !self.selection.contains(&i) {
+                                    self.selection.clear();
+                                    self.selection.insert(i);
+                                }
+                            }
                         }
                     }
                 }
 
                 // 드래그 중
                 if self.dragging == Some(i) {
-                    if let (Some(pos), Some(start)) = (pointer_pos, self.drag_start) {
-                        let delta = pos - start;
-                        obj.position[0] += delta.x / (10.0 * self.zoom);
-                        obj.position[1] += delta.y / (10.0 * self.zoom);
+                    let start = self.drag_start.filter(|start| {
+                        self.drag_moved || pointer_pos.is_some_and(|pos| (pos - *start).length() >= DRAG_THRESHOLD)
+                    });
+                    if let (Some(pos), Some(start)) = (pointer_pos, start) {
+                        self.drag_moved = true;
+                        let delta = view.delta_to_world(pos - start);
+                        self.drag_raw[0] += delta[0];
+                        self.drag_raw[1] += delta[1];
                         self.drag_start = Some(pos);
+
+                        // Alt를 누르고 있거나 오브젝트가 스냅을 끄면 스냅 무시
+                        let free = ctx.input(|i| i.modifiers.alt) || !obj.snap;
+                        let position = if self.settings.snap_to_grid && !free {
+                            self.drag_raw.map(|v| (v / snap_step).round() * snap_step)
+                        } else {
+                            self.drag_raw
+                        };
+                        // 가까운 다른 오브젝트의 가장자리에 딱 맞춰 붙임
+                        let position = if self.settings.snap_edges && !free {
+                            let threshold = EDGE_SNAP / unit;
+                            let body = Transform { position, ..world }.centered(obj.pivot).aabb();
+                            let mut best: [Option<(f32, f32)>; 2] = [None, None];
+                            for (j, other) in bodies.iter().enumerate() {
+                                if j == i || !visible[j] || self.selection.contains(&j) {
+                                    continue;
+                                }
+                                let other = other.aabb();
+                                let spans = [
+                                    (body.x_range(), other.x_range(), body.y_range(), other.y_range()),
+                                    (body.y_range(), other.y_range(), body.x_range(), other.x_range()),
+                                ];
+                                for (axis, (own, theirs, across, their_across)) in spans.into_iter().enumerate() {
+                                    // 반대 축으로 떨어져 있으면 이웃이 아님
+                                    if across.min > their_across.max + threshold || their_across.min > across.max + threshold {
+                                        continue;
+                                    }
+                                    for (edge, target) in [
+                                        (own.min, theirs.max),
+                                        (own.max, theirs.min),
+                                        (own.min, theirs.min),
+                                        (own.max, theirs.max),
+                                    ] {
+                                        let d = target - edge;
+                                        if d.abs() <= threshold && best[axis].is_none_or(|(b, _)| d.abs() < b.abs()) {
+                                            best[axis] = Some((d, target));
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some((_, x)) = best[0] {
+                                edge_guides.push([egui::pos2(x, body.top()), egui::pos2(x, body.bottom())]);
+                            }
+                            if let Some((_, y)) = best[1] {
+                                edge_guides.push([egui::pos2(body.left(), y), egui::pos2(body.right(), y)]);
+                            }
+                            [
+                                position[0] + best[0].map_or(0.0, |(d, _)| d),
+                                position[1] + best[1].map_or(0.0, |(d, _)| d),
+                            ]
+                        } else {
+                            position
+                        };
+
+                        // 여러 개 선택된 상태면 선택 전체를 같은 만큼 옮김
+                        if self.selection.len() > 1 && self.selection.contains(&i) {
+                            group_move = Some([position[0] - world.position[0], position[1] - world.position[1]]);
+                        } else {
+                            // 고정된 오브젝트를 끌면 오프셋이 바뀜
+                            obj.set_world_position(parent_worlds[i], anchor_worlds[i], Transform { position, ..world });
+                        }
                     }
 
                     // 마우스 뗐을 때
-                    if response.drag_released() {
+                    if response.drag_stopped() {
                         self.dragging = None;
                         self.drag_start = None;
                     }
                 }
 
                 // 오브젝트 그리기
-                let angle = obj.rotation;
-                let half_w = size_x / 2.0;
-                let half_h = size_y / 2.0;
-                let points = [
-                    (-half_w, -half_h),
-                    (half_w, -half_h),
-                    (half_w, half_h),
-                    (-half_w, half_h),
-                ];
-                let rotated: Vec<egui::Pos2> = points
-                    .iter()
-                    .map(|(dx, dy)| {
-                        let rx = dx * angle.cos() - dy * angle.sin();
-                        let ry = dx * angle.sin() + dy * angle.cos();
-                        egui::pos2(center.x + rx, center.y + ry)
-                    })
-                    .collect();
-
-                if ctx.input(|i| i.pointer.secondary_down()) {
-                    if let Some(current) = response.interact_pointer_pos() {
-                        if let Some(start) = self.pan_start {
-                            let delta = current - start;
-                            self.view_offset[0] += delta.x;
-                            self.view_offset[1] += delta.y;
-                            self.pan_start = Some(current);
-                        } else {
-                            self.pan_start = Some(current);
-                        }
+                // 메모가 있는 오브젝트는 모서리에 표시하고 올리면 툴팁으로 보여줌
+                if !obj.note.is_empty() {
+                    notes.push(bounding.left_top());
+                    if pointer_pos.is_some_and(contains) {
+                        hovered_note = Some(obj.note.clone());
                     }
-                } else {
-                    self.pan_start = None;
                 }
 
-                if let Some(path) = &obj.image_path {
-                    if let Some(tex) = self.image_cache.get(path) {
-                        let size = egui::vec2(size_x, size_y);
-                        let pos = egui::pos2(center.x - size_x / 2.0, center.y - size_y / 2.0);
-                        painter.image(
-                            tex.id(),
-                            egui::Rect::from_min_size(pos, size),
-                            egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(1.0, 1.0)),
-                            egui::Color32::WHITE,
-                        );
-                        continue;
-                    }
+                // 원점(피벗) 점도 이미지 위에 보이도록 나중에 그림
+                if self.settings.show_centers {
+                    centers.push((view.to_screen(world.position), self.selection.contains(&i)));
                 }
 
-                ctx.input(|i| {
-                    let step = 10.0;
-                    if i.key_down(egui::Key::W) {
-                        self.view_offset[1] += step;
-                    }
-                    if i.key_down(egui::Key::S) {
-                        self.view_offset[1] -= step;
+                // 디버깅용 id / 인덱스 표시 (이미지 위에도 보이도록 그리기 순서 무시)
+                if self.settings.show_ids {
+                    overlay_ids.push((bounding.center_bottom(), format!("id {} · #{}", obj.id, i)));
+                }
+
+                if let Some(path) = &obj.image_path {
+                    // 화면 근처에 들어온 오브젝트의 이미지만 불러옴
+                    let near = bounding.intersects(rect);
+                    if near && !self.image_cache.contains_key(path) && !self.failed_images.contains(path) {
+                        self.textures.request(path);
                     }
-                    if i.key_down(egui::Key::A) {
-                        self.view_offset[0] += step;
+                    if let Some(tex) = self.image_cache.get(path) {
+                        if let Some(used) = self.texture_used.get_mut(path) {
+                            *used = frame;
+                        }
+                        // 음수 스케일은 꼭짓점 순서가 뒤집히므로 텍스처도 따라서 뒤집힘
+                        let uv = obj.uv();
+                        if near && self.textures.svg_size(path).is_some() {
+                            let [w, h] = tex.size().map(|v| v as f32);
+                            let texels = (bounding.width() / (uv.width().abs() * w)).max(bounding.height() / (uv.height().abs() * h));
+                            let wanted = texels * w.max(h);
+                            let entry = svg_wanted.entry(path.clone()).or_insert(0.0);
+                            *entry = entry.max(wanted);
+                        }
+                        let mut tint = egui::Color32::from_rgba_unmultiplied(obj.tint[0], obj.tint[1], obj.tint[2], obj.tint[3]);
+                        if !inside[i] {
+                            tint = tint.gamma_multiply(OUTSIDE_GROUP_ALPHA);
+                        }
+                        let mut mesh = egui::Mesh::with_texture(tex.id());
+                        for (pos, uv) in quad.into_iter().zip([uv.left_top(), uv.right_top(), uv.right_bottom(), uv.left_bottom()]) {
+                            mesh.vertices.push(egui::epaint::Vertex { pos, uv, color: tint });
+                        }
+                        mesh.indices.extend([0, 1, 2, 0, 2, 3]);
+                        painter.add(mesh);
+                        continue;
                     }
-                    if i.key_down(egui::Key::D) {
-                        self.view_offset[0] -= step;
+                    // 불러오는 동안에는 같은 자리에 회색 사각형을 채워 그림. 텍스처는 이 사각형에
+                    // 그대로 늘려 그리므로 다 불러와도 크기가 바뀌지 않음
+                    if near && !self.failed_images.contains(path) {
+                        let time = ctx.input(|inp| inp.time) as f32;
+                        let mut mesh = egui::Mesh::default();
+                        for pos in quad {
+                            // 왼쪽에서 오른쪽으로 밝은 띠가 지나감
+                            let wave = (time * 3.0 - pos.x * 0.02).sin() * 0.5 + 0.5;
+                            let v = (PLACEHOLDER_GRAY + PLACEHOLDER_SHIMMER * wave) as u8;
+                            let mut color = egui::Color32::from_gray(v);
+                            if !inside[i] {
+                                color = color.gamma_multiply(OUTSIDE_GROUP_ALPHA);
+                            }
+                            mesh.colored_vertex(pos, color);
+                        }
+                        mesh.add_triangle(0, 1, 2);
+                        mesh.add_triangle(0, 2, 3);
+                        painter.add(mesh);
+                        shimmering = true;
                     }
-                });
+                }
 
-                let stroke_color = if self.selected == Some(i) {
-                    egui::Color32::YELLOW
+                // 이미지가 깨진 오브젝트는 자홍색 외곽선과 "!" 표시
+                let broken = obj.image_path.as_ref().is_some_and(|p| self.failed_images.contains(p));
+                let [r, g, b, a] = if self.selection.contains(&i) {
+                    self.prefs.selected_color
+                } else if broken {
+                    [255, 0, 255, 255]
                 } else {
-                    egui::Color32::LIGHT_BLUE
+                    self.prefs.outline_color
                 };
+                let mut stroke_color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+                if !inside[i] {
+                    stroke_color = stroke_color.gamma_multiply(OUTSIDE_GROUP_ALPHA);
+                }
+                if broken {
+                    painter.text(
+                        bounding.right_top(),
+                        egui::Align2::RIGHT_TOP,
+                        "!",
+                        egui::FontId::proportional(14.0),
+                        egui::Color32::from_rgb(255, 0, 255),
+                    );
+                }
 
-                painter.add(egui::Shape::closed_line(
-                    rotated,
-                    egui::Stroke::new(2.0, stroke_color),
-                ));
+                // 오브젝트마다 Vec을 만들지 않도록 네 변을 선분으로 그림
+                let stroke = egui::Stroke::new(self.prefs.stroke_width(self.zoom), stroke_color);
+                for k in 0..4 {
+                    painter.line_segment([quad[k], quad[(k + 1) % 4]], stroke);
+                }
 
                 painter.text(
                     center,
@@ -263,6 +2884,423 @@ impl eframe::App for StarEditor {
                     egui::Color32::WHITE,
                 );
             }
+
+            if shimmering {
+                ctx.request_repaint();
+            }
+
+            // 줌이 많이 바뀐 SVG는 화면 크기에 맞춰 다시 래스터화. 새 텍스처가 올 때까지는 예전 것을 그림
+            for (path, wanted) in svg_wanted {
+                let Some(side) = self.textures.svg_size(&path) else {
+                    continue;
+                };
+                let size = (wanted.ceil() as u32).next_power_of_two().clamp(loader::SVG_MIN_SIZE, loader::SVG_MAX_SIZE);
+                let far = wanted > side as f32 * 1.5 || wanted < side as f32 / 4.0;
+                if far && size != side && !self.textures.is_loading(&path) {
+                    self.textures.request_sized(&path, Some(size));
+                }
+            }
+
+            // 프레임 간격을 부드럽게 평균 내서 표시 (표시 중에는 계속 다시 그림)
+            if self.prefs.show_fps {
+                let dt = ctx.input(|i| i.unstable_dt);
+                self.frame_time = if self.frame_time == 0.0 { dt } else { self.frame_time * 0.9 + dt * 0.1 };
+                painter.text(
+                    rect.right_top() + egui::vec2(-8.0, 8.0),
+                    egui::Align2::RIGHT_TOP,
+                    format!("{:.0} FPS  {:.1} ms", 1.0 / self.frame_time.max(1e-4), self.frame_time * 1000.0),
+                    egui::FontId::monospace(11.0),
+                    egui::Color32::LIGHT_GREEN,
+                );
+                ctx.request_repaint();
+            }
+
+            if let Some(ids) = &self.solo {
+                painter.text(
+                    rect.left_top() + egui::vec2(8.0, 8.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("SOLO ({} object(s))", ids.len()),
+                    egui::FontId::proportional(14.0),
+                    egui::Color32::from_rgb(255, 160, 0),
+                );
+                painter.rect_stroke(rect.shrink(1.0), 0.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 160, 0)));
+            }
+
+            // 포인터 아래에 무엇이 있는지 커서 모양으로 알려 줌
+            let over_handle = |pos: egui::Pos2, at: egui::Pos2| (pos - at).length() <= HANDLE_RADIUS;
+            let cursor = if self.pan_start.is_some() {
+                Some(egui::CursorIcon::Grabbing)
+            } else if let Some((handle, _, _)) = self.group_drag {
+                Some(handle.cursor())
+            } else if self.pivot_drag.is_some() {
+                Some(egui::CursorIcon::Crosshair)
+            } else if self.dragging.is_some() {
+                Some(egui::CursorIcon::Move)
+            } else if let Some(pos) = response.hover_pos().filter(|_| self.placing.is_none()) {
+                let handle = group_handles.and_then(|(scale_at, rotate_at)| {
+                    if over_handle(pos, scale_at) {
+                        Some(GroupHandle::Scale)
+                    } else if over_handle(pos, rotate_at) {
+                        Some(GroupHandle::Rotate)
+                    } else {
+                        None
+                    }
+                });
+                let pivot = self.selected.filter(|&i| i < bodies.len() && visible[i] && !locked[i])
+                    .is_some_and(|i| over_handle(pos, pivot_screen(i)));
+                let object = (0..bodies.len()).any(|i| visible[i] && !locked[i] && bodies[i].contains(view.to_world(pos)));
+                if let Some(handle) = handle {
+                    Some(handle.cursor())
+                } else if pivot {
+                    Some(egui::CursorIcon::Crosshair)
+                } else if object && (!self.selection_locked || self.selection.iter().any(|&i| bodies.get(i).is_some_and(|b| b.contains(view.to_world(pos))))) {
+                    Some(egui::CursorIcon::Move)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+            if let Some(cursor) = cursor {
+                ctx.set_cursor_icon(cursor);
+            }
+
+            if let Some(delta) = group_move {
+                self.transform_selection(|t| Transform {
+                    position: [t.position[0] + delta[0], t.position[1] + delta[1]],
+                    ..t
+                });
+            }
+
+            // Ctrl을 누른 채 끌기 시작하면 사본을 만들어 사본을 끌고 원본은 제자리에
+            if duplicate_drag {
+                if let Some(i) = self.dragging {
+                    self.select(i);
+                    self.duplicate_selection([0.0, 0.0]);
+                    self.dragging = self.selected;
+                }
+            }
+
+            // 가장자리 맞춤 안내선 (월드 좌표)
+            for [a, b] in edge_guides {
+                let to_pos = |p: egui::Pos2| view.to_screen([p.x, p.y]);
+                painter.line_segment([to_pos(a), to_pos(b)], egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 220, 255)));
+            }
+
+            // 자식 → 부모 원점 연결선 (이번 프레임에 옮긴 위치 반영)
+            if self.settings.show_links {
+                let worlds = self.scene.world_transforms();
+                let to_pos = |p: [f32; 2]| view.to_screen(p);
+                let stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(70));
+                for (i, obj) in self.scene.objects.iter().enumerate() {
+                    let Some(p) = obj.parent.and_then(|p| self.scene.index_of(p)).filter(|_| visible[i]) else {
+                        continue;
+                    };
+                    let (child, parent) = (to_pos(worlds[i].position), to_pos(worlds[p].position));
+                    painter.line_segment([child, parent], stroke);
+                    painter.circle_stroke(parent, 3.0, stroke);
+                }
+            }
+
+            for (pos, selected) in centers {
+                let [r, g, b, a] = if selected { self.prefs.selected_color } else { self.prefs.outline_color };
+                painter.circle_filled(pos, 2.5, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
+                painter.circle_stroke(pos, 2.5, egui::Stroke::new(1.0, egui::Color32::BLACK));
+            }
+
+            for pos in notes {
+                painter.circle_filled(pos, 4.0, egui::Color32::from_rgb(120, 200, 255));
+            }
+            if let Some(note) = hovered_note.filter(|_| response.hovered() && self.dragging.is_none()) {
+                egui::show_tooltip_text(ctx, egui::Id::new("scene_note"), note);
+            }
+
+            for (pos, text) in overlay_ids {
+                let galley = painter.layout_no_wrap(text, egui::FontId::monospace(9.0), egui::Color32::BLACK);
+                let label = egui::Align2::CENTER_TOP.anchor_rect(egui::Rect::from_min_size(pos, galley.size()));
+                painter.rect_filled(label.expand(1.0), 2.0, egui::Color32::from_rgb(255, 220, 0));
+                painter.galley(label.min, galley, egui::Color32::BLACK);
+            }
+
+            // 더블클릭한 오브젝트를 지금 단계에서 감싸는 그룹으로 들어감
+            if response.double_clicked() && self.placing.is_none() {
+                let hit = press_origin.and_then(|pos| {
+                    (0..bodies.len()).rev().find(|&i| visible[i] && !locked[i] && bodies[i].contains(view.to_world(pos)))
+                });
+                let mut target = hit.map(|i| self.scene.objects[i].id);
+                for _ in 0..self.scene.objects.len() {
+                    let parent = target.and_then(|id| self.scene.index_of(id)).and_then(|i| self.scene.objects[i].parent);
+                    if parent.is_none() || parent == self.entered_group || parent.is_some_and(|p| self.scene.index_of(p).is_none()) {
+                        break;
+                    }
+                    target = parent;
+                }
+                if let Some(group) = target.filter(|&id| self.scene.objects.iter().any(|o| o.parent == Some(id))) {
+                    self.entered_group = Some(group);
+                    self.clear_selection();
+                }
+            }
+
+            if response.clicked() && self.placing.is_none() && !self.selection_locked {
+                let additive = ctx.input(|i| i.modifiers.command);
+                match clicked_object {
+                    Some(i) if additive => self.toggle_select(i),
+                    Some(i) => self.select(i),
+                    None if !additive => self.clear_selection(),
+                    None => {}
+                }
+            }
+
+            let to_screen_pos = |p: [f32; 2]| view.to_screen(p);
+
+            // 새로 선택된 오브젝트 위치를 퍼지는 원으로 표시
+            if self.selected != self.pinged_selection {
+                self.pinged_selection = self.selected;
+                self.ping = self.selected.map(|i| (i, ctx.input(|inp| inp.time)));
+            }
+            if let Some((i, start)) = self.ping {
+                const PING_SECONDS: f64 = 0.6;
+                let t = ((ctx.input(|inp| inp.time) - start) / PING_SECONDS) as f32;
+                match worlds.get(i) {
+                    Some(world) if t < 1.0 => {
+                        let base = world.size()[0].abs().max(world.size()[1].abs()) / 2.0 * ppu * self.zoom;
+                        let alpha = ((1.0 - t) * 255.0) as u8;
+                        painter.circle_stroke(
+                            to_screen_pos(world.position),
+                            base + t * 40.0,
+                            egui::Stroke::new(2.0, egui::Color32::from_rgba_unmultiplied(255, 220, 0, alpha)),
+                        );
+                        ctx.request_repaint();
+                    }
+                    _ => self.ping = None,
+                }
+            }
+
+            // 월드 원점 표시 (화면 밖이면 가장자리에 방향 화살표)
+            let origin = to_screen_pos([0.0, 0.0]);
+            let label_font = egui::FontId::monospace(10.0);
+            if rect.contains(origin) {
+                let (x_axis, y_axis) = (view.rotation * egui::vec2(10.0, 0.0), view.rotation * egui::vec2(0.0, 10.0));
+                painter.line_segment(
+                    [origin - x_axis, origin + x_axis],
+                    egui::Stroke::new(1.5, egui::Color32::from_rgb(220, 80, 80)),
+                );
+                painter.line_segment(
+                    [origin - y_axis, origin + y_axis],
+                    egui::Stroke::new(1.5, egui::Color32::from_rgb(80, 200, 80)),
+                );
+                painter.text(origin + egui::vec2(4.0, 4.0), egui::Align2::LEFT_TOP, "(0,0)", label_font, egui::Color32::GRAY);
+            } else {
+                let inner = rect.shrink(20.0);
+                let edge = origin.clamp(inner.min, inner.max);
+                let dir = (origin - edge).normalized() * 14.0;
+                painter.arrow(edge - dir, dir, egui::Stroke::new(1.5, egui::Color32::GRAY));
+                painter.text(edge - dir * 1.5, egui::Align2::CENTER_CENTER, "(0,0)", label_font, egui::Color32::GRAY);
+            }
+
+            // 비교 중인 씬에서 이동한 오브젝트 화살표
+            if self.compare.other.is_some() {
+                for (_, from, to) in &self.compare.result.moved {
+                    let start = to_screen_pos(*from);
+                    painter.arrow(start, to_screen_pos(*to) - start, egui::Stroke::new(1.5, egui::Color32::LIGHT_GREEN));
+                }
+            }
+
+            // 선택된 오브젝트의 AABB (여러 개면 전체를 감싸는 상자와 핸들)
+            if let (Some(b), Some((scale_at, rotate_at))) = (group_box, group_handles) {
+                let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 200, 0));
+                view.outline(&painter, b, stroke);
+                painter.rect_filled(egui::Rect::from_center_size(scale_at, egui::Vec2::splat(SCALE_HANDLE_SIZE)), 0.0, stroke.color);
+                painter.line_segment([view.to_screen([b.center().x, b.top()]), rotate_at], stroke);
+                painter.circle_filled(rotate_at, HANDLE_RADIUS, stroke.color);
+            } else if let Some(body) = self.selected.and_then(|i| bodies.get(i)) {
+                view.outline(
+                    &painter,
+                    body.aabb(),
+                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255, 255, 0, 120)),
+                );
+            }
+
+            // 피벗 핸들
+            if let Some(i) = self.selected.filter(|&i| i < worlds.len() && visible[i]) {
+                let at = pivot_screen(i);
+                let active = self.pivot_drag == Some(i)
+                    || pointer_pos.is_some_and(|pos| (pos - at).length() <= HANDLE_RADIUS);
+                let color = if active { egui::Color32::WHITE } else { egui::Color32::from_rgb(255, 140, 0) };
+                painter.circle_filled(at, PIVOT_DOT_RADIUS, color);
+                painter.circle_stroke(at, HANDLE_RADIUS, egui::Stroke::new(1.5, color));
+            }
+
+            // 겹치는 오브젝트 표시 (디버그용)
+            if self.settings.show_overlaps {
+                let bounds: Vec<egui::Rect> = (0..worlds.len())
+                    .filter(|&i| visible[i])
+                    .map(|i| bodies[i].aabb())
+                    .collect();
+                let stroke = egui::Stroke::new(1.5, egui::Color32::RED);
+                for a in 0..bounds.len() {
+                    for b in (a + 1)..bounds.len() {
+                        if !bounds[a].intersects(bounds[b]) {
+                            continue;
+                        }
+                        let overlap = bounds[a].intersect(bounds[b]);
+                        view.fill(&painter, overlap, egui::Color32::from_rgba_unmultiplied(255, 0, 0, 60));
+                        view.outline(&painter, bounds[a], stroke);
+                        view.outline(&painter, bounds[b], stroke);
+                    }
+                }
+            }
+
+            // 오브젝트가 하나도 안 보이면 되돌아가는 버튼
+            if let Some(b) = lost {
+                let button = egui::Rect::from_center_size(rect.center(), egui::vec2(220.0, 28.0));
+                if ui.put(button, egui::Button::new("Nothing in view — Recenter on scene")).clicked() {
+                    self.set_view([b.center().x, b.center().y], self.zoom);
+                }
+            }
         });
+
+        let mut jump = None;
+        let mut open = self.history.open;
+        egui::Window::new("History")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("⟲ Undo").clicked() {
+                        jump = Some(Action::Undo);
+                    }
+                    if ui.button("⟳ Redo").clicked() {
+                        jump = Some(Action::Redo);
+                    }
+                });
+                ui.separator();
+                if let Some(i) = self.history.ui(ui) {
+                    let scene = self.history.jump(i).cloned();
+                    self.restore(scene);
+                }
+            });
+        self.history.open = open;
+        if let Some(action) = jump {
+            self.run_action(action, ctx);
+        }
+
+        // 드래그나 글자 입력이 끝난 뒤 바뀐 점이 있으면 한 단계로 기록
+        let pointer_down = ctx.input(|i| i.pointer.any_down());
+        let focused = ctx.memory(|m| m.focused());
+        self.history.commit(pointer_down, focused, &mut self.edit_label, &self.scene);
+
+        if let Some((path, message)) = &self.load_error {
+            let mut close = false;
+            egui::Window::new("Could Not Open Scene")
+                .collapsible(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.label(format!("{} could not be parsed:", path));
+                    ui.add(egui::Label::new(egui::RichText::new(message).monospace()).wrap(false));
+                    close = ui.button("OK").clicked();
+                });
+            if close {
+                self.load_error = None;
+            }
+        }
+
+        if let Some(path) = self.overwrite_prompt.clone() {
+            let (mut save_as, mut overwrite, mut cancel) = (false, false, false);
+            egui::Window::new("Saved by a Newer Version")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} uses scene format {}, but this build only understands format {}. \
+                         Overwriting it may drop data this build does not know about.",
+                        path,
+                        self.newer_format.unwrap_or_default(),
+                        save::SCENE_VERSION,
+                    ));
+                    ui.horizontal(|ui| {
+                        save_as = ui.button("Save As...").clicked();
+                        overwrite = ui.button("Overwrite").clicked();
+                        cancel = ui.button("Cancel").clicked();
+                    });
+                });
+            if save_as || overwrite || cancel {
+                self.overwrite_prompt = None;
+            }
+            if save_as {
+                self.path_prompt.open(PathAction::SaveAs, &path);
+            } else if overwrite {
+                self.save_to(&path, true, ctx);
+            }
+        }
+
+        if self.recovery_prompt {
+            egui::Window::new("Recover Unsaved Work?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.label("The editor closed unexpectedly last time. Restore the scene from the crash backup?");
+                    ui.horizontal(|ui| {
+                        if ui.button("Recover").clicked() {
+                            match save::load_scene(recovery::RECOVERY_PATH) {
+                                Ok(scene) => self.replace_scene(scene, recovery::RECOVERY_PATH, ctx),
+                                Err(message) => self.load_error = Some((recovery::RECOVERY_PATH.to_string(), message)),
+                            }
+                            recovery::discard();
+                            self.recovery_prompt = false;
+                        }
+                        if ui.button("Discard").clicked() {
+                            recovery::discard();
+                            self.recovery_prompt = false;
+                        }
+                    });
+                });
+        }
+
+        // 크래시 대비 스냅샷은 1초에 한 번만 갱신 (복구 여부를 고르기 전에는 덮어쓰지 않음)
+        let now = ctx.input(|i| i.time);
+        if !self.recovery_prompt && now - self.last_snapshot >= 1.0 {
+            let ron = ron::ser::to_string(&self.scene).unwrap_or_default();
+            let dirty = ron != self.saved_ron;
+            if dirty != self.dirty {
+                self.dirty = dirty;
+                let title = format!("Star Editor - {}{}", self.current_path, if dirty { " *" } else { "" });
+                ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+            }
+            recovery::update_snapshot(ron);
+            self.last_snapshot = now;
+        }
+
+        match self.path_prompt.show(ctx) {
+            Some((PathAction::Open, path)) => self.open(&path),
+            Some((PathAction::SaveAs, path)) => self.request_save(&path, ctx),
+            Some((PathAction::SaveCopy, path)) => self.save_to(&path, false, ctx),
+            Some((PathAction::ExportPrefab, path)) => self.export_prefab(&path, ctx),
+            Some((PathAction::ExportManifest, path)) => self.export_manifest(&path, ctx),
+            Some((PathAction::ImportCsv, path)) => self.import_csv(&path, ctx),
+            Some((PathAction::InsertPrefab, path)) => self.insert_prefab(&path, ctx),
+            Some((PathAction::SetImage, path)) => self.set_selection_image(Some(path), self.prefs.auto_name),
+            None => {}
+        }
+
+        self.toasts.show(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth_scroll_steps_once_per_notch() {
+        let mut accumulated = 0.0;
+        // 한 칸 분량을 열 프레임에 나눠 받아도 한 단계만
+        let steps: i32 = (0..10).map(|_| pixel_zoom_steps(&mut accumulated, PIXEL_ZOOM_SCROLL / 10.0 + 0.01)).sum();
+        assert_eq!(steps, 1);
+        assert_eq!(pixel_zoom_steps(&mut accumulated, PIXEL_ZOOM_SCROLL * 2.0), 2);
+        // 반대로 돌리면 모아 둔 양은 버리고 새로 셈
+        assert_eq!(pixel_zoom_steps(&mut accumulated, -PIXEL_ZOOM_SCROLL / 2.0), 0);
+        assert_eq!(pixel_zoom_steps(&mut accumulated, -PIXEL_ZOOM_SCROLL / 2.0), -1);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,184 @@
+use eframe::egui;
+use crate::diff;
+use crate::model::Scene;
+
+// 이보다 오래된 기록은 버림
+const MAX_ENTRIES: usize = 100;
+
+pub struct Entry {
+    pub label: String,
+    pub scene: Scene,
+}
+
+#[derive(Default)]
+pub struct History {
+    pub open: bool,
+    entries: Vec<Entry>,
+    current: usize,
+    // 마지막으로 기록한 때 포커스가 있던 입력칸
+    edit_focus: Option<egui::Id>,
+}
+
+// 누르고 있거나 같은 입력칸에 계속 쓰는 중이면 기다림. 포커스가 다른 칸으로 옮겨가면 앞 칸의 편집을 기록
+fn settled(pointer_down: bool, focused: Option<egui::Id>, edit_focus: Option<egui::Id>) -> bool {
+    let typing = focused.is_some() && focused == edit_focus;
+    !pointer_down && !typing
+}
+
+// 두 상태의 차이로 기록 이름을 만듦 ("Move Object 3", "Add 2 objects" 등)
+pub fn describe(base: &Scene, scene: &Scene) -> String {
+    let changes = diff::diff(base, scene);
+    let name = |id: usize| {
+        scene.objects.iter().chain(&base.objects)
+            .find(|o| o.id == id)
+            .map_or(format!("#{}", id), |o| o.name.clone())
+    };
+    let summary = |verb: &str, ids: &[usize]| match ids {
+        [id] => format!("{} {}", verb, name(*id)),
+        _ => format!("{} {} objects", verb, ids.len()),
+    };
+    let only_moved = changes.modified.iter().all(|(_, fields)| fields.iter().all(|f| f.starts_with("position:")));
+    if !changes.added.is_empty() {
+        summary("Add", &changes.added)
+    } else if !changes.removed.is_empty() {
+        summary("Delete", &changes.removed)
+    } else if !changes.modified.is_empty() {
+        let ids: Vec<usize> = changes.modified.iter().map(|(id, _)| *id).collect();
+        summary(if only_moved { "Move" } else { "Edit" }, &ids)
+    } else if base.objects.iter().map(|o| o.id).ne(scene.objects.iter().map(|o| o.id)) {
+        "Reorder objects".to_string()
+    } else {
+        "Edit scene settings".to_string()
+    }
+}
+
+impl History {
+    pub fn reset(&mut self, label: impl Into<String>, scene: &Scene) {
+        self.entries = vec![Entry { label: label.into(), scene: scene.clone() }];
+        self.current = 0;
+    }
+
+    // 되돌린 뒤 새로 기록하면 다시 실행할 기록은 사라짐
+    pub fn record(&mut self, label: impl Into<String>, scene: &Scene) {
+        self.entries.truncate(self.current + 1);
+        self.entries.push(Entry { label: label.into(), scene: scene.clone() });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.current = self.entries.len() - 1;
+    }
+
+    // 드래그나 글자 입력이 끝난 프레임에 바뀐 점을 한 단계로 기록. 기록했으면 true
+    pub fn commit(&mut self, pointer_down: bool, focused: Option<egui::Id>, label: &mut Option<String>, scene: &Scene) -> bool {
+        if !settled(pointer_down, focused, self.edit_focus) {
+            return false;
+        }
+        self.edit_focus = focused;
+        let label = label.take();
+        let Some(base) = self.current().filter(|base| *base != scene) else {
+            return false;
+        };
+        let label = label.unwrap_or_else(|| describe(base, scene));
+        self.record(label, scene);
+        true
+    }
+
+    pub fn current(&self) -> Option<&Scene> {
+        self.entries.get(self.current).map(|e| &e.scene)
+    }
+
+    pub fn jump(&mut self, index: usize) -> Option<&Scene> {
+        if index >= self.entries.len() || index == self.current {
+            return None;
+        }
+        self.current = index;
+        self.current()
+    }
+
+    pub fn undo(&mut self) -> Option<&Scene> {
+        self.jump(self.current.checked_sub(1)?)
+    }
+
+    pub fn redo(&mut self) -> Option<&Scene> {
+        self.jump(self.current + 1)
+    }
+
+    // 누른 기록의 인덱스를 돌려줌. 현재 위치보다 뒤의 기록은 흐리게 표시
+    pub fn ui(&self, ui: &mut egui::Ui) -> Option<usize> {
+        let mut jump = None;
+        egui::ScrollArea::vertical().max_height(300.0).stick_to_bottom(true).show(ui, |ui| {
+            for (i, entry) in self.entries.iter().enumerate() {
+                let mut text = egui::RichText::new(&entry.label);
+                if i > self.current {
+                    text = text.weak().italics();
+                }
+                if ui.selectable_label(i == self.current, text).clicked() {
+                    jump = Some(i);
+                }
+            }
+        });
+        jump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> (History, Scene) {
+        let scene = Scene::default();
+        let mut history = History::default();
+        history.reset("Open", &scene);
+        (history, scene)
+    }
+
+    #[test]
+    fn one_drag_is_one_step() {
+        let (mut history, mut scene) = history();
+        for _ in 0..30 {
+            scene.pixels_per_unit += 1.0;
+            assert!(!history.commit(true, None, &mut None, &scene));
+        }
+        assert!(history.commit(false, None, &mut None, &scene));
+        assert_eq!(history.entries.len(), 2);
+    }
+
+    #[test]
+    fn typing_in_one_field_is_one_step() {
+        let (mut history, mut scene) = history();
+        let field = Some(egui::Id::new("name"));
+        // 포커스를 얻은 프레임에는 아직 바뀐 점이 없음
+        assert!(!history.commit(false, field, &mut None, &scene));
+        for _ in 0..5 {
+            scene.pixels_per_unit += 1.0;
+            assert!(!history.commit(false, field, &mut None, &scene));
+        }
+        assert!(history.commit(false, None, &mut None, &scene));
+        assert_eq!(history.entries.len(), 2);
+    }
+
+    #[test]
+    fn switching_fields_records_each_field() {
+        let (mut history, mut scene) = history();
+        let (a, b) = (Some(egui::Id::new("a")), Some(egui::Id::new("b")));
+        history.commit(false, a, &mut None, &scene);
+        scene.pixels_per_unit += 1.0;
+        history.commit(false, a, &mut None, &scene);
+        // 다른 칸을 누르는 동안은 기다리고, 놓은 뒤 포커스가 바뀐 것을 보고 기록
+        assert!(!history.commit(true, b, &mut None, &scene));
+        assert!(history.commit(false, b, &mut None, &scene));
+        scene.background_color[0] ^= 1;
+        assert!(!history.commit(false, b, &mut None, &scene));
+        assert!(history.commit(false, None, &mut None, &scene));
+        assert_eq!(history.entries.len(), 3);
+    }
+
+    #[test]
+    fn unchanged_frame_records_nothing() {
+        let (mut history, scene) = history();
+        let mut label = Some("Nothing".to_string());
+        assert!(!history.commit(false, None, &mut label, &scene));
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(label, None);
+    }
+}
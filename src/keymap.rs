@@ -0,0 +1,445 @@
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// 씬 뷰의 마우스 조작 (바꿀 수 없으므로 단축키 표에만 보여줌)
+const MOUSE_GESTURES: &[(Modifiers, &str, &str)] = &[
+    (Modifiers::NONE, "Drag", "Move objects"),
+    (Modifiers::COMMAND, "Drag", "Duplicate and move the copy"),
+    (Modifiers::ALT, "Drag", "Move without snapping"),
+    (Modifiers::SHIFT, "Drag", "Snap to whole grid cells"),
+    (Modifiers::COMMAND, "Click", "Add to selection"),
+    (Modifiers::NONE, "Double-click", "Enter group"),
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    SelectPrevious,
+    SelectNext,
+    CycleNext,
+    CyclePrevious,
+    Rename,
+    Save,
+    SaveAs,
+    SaveCopy,
+    Open,
+    AddObject,
+    ImportSheet,
+    ExportPrefab,
+    InsertPrefab,
+    ExportManifest,
+    ImportCsv,
+    Undo,
+    Redo,
+    Delete,
+    Group,
+    Ungroup,
+    FocusSelected,
+    FrameSelected,
+    FrameAll,
+    ActualSize,
+    Duplicate,
+    DuplicateInPlace,
+    Copy,
+    Paste,
+    PasteInPlace,
+    Solo,
+    MirrorHorizontal,
+    MirrorVertical,
+    Validate,
+    History,
+    ToggleHierarchy,
+    ToggleInspector,
+    ToggleGrid,
+    ToggleSnap,
+    Preferences,
+    ShortcutHelp,
+    CommandPalette,
+    ZoomIn,
+    ZoomOut,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    Cancel,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = &[
+        Action::SelectPrevious,
+        Action::SelectNext,
+        Action::CycleNext,
+        Action::CyclePrevious,
+        Action::Rename,
+        Action::Save,
+        Action::SaveAs,
+        Action::SaveCopy,
+        Action::Open,
+        Action::AddObject,
+        Action::ImportSheet,
+        Action::ExportPrefab,
+        Action::InsertPrefab,
+        Action::ExportManifest,
+        Action::ImportCsv,
+        Action::Undo,
+        Action::Redo,
+        Action::Delete,
+        Action::Group,
+        Action::Ungroup,
+        Action::FocusSelected,
+        Action::FrameSelected,
+        Action::FrameAll,
+        Action::ActualSize,
+        Action::Duplicate,
+        Action::DuplicateInPlace,
+        Action::Copy,
+        Action::Paste,
+        Action::PasteInPlace,
+        Action::Solo,
+        Action::MirrorHorizontal,
+        Action::MirrorVertical,
+        Action::Validate,
+        Action::History,
+        Action::ToggleHierarchy,
+        Action::ToggleInspector,
+        Action::ToggleGrid,
+        Action::ToggleSnap,
+        Action::Preferences,
+        Action::ShortcutHelp,
+        Action::CommandPalette,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::PanUp,
+        Action::PanDown,
+        Action::PanLeft,
+        Action::PanRight,
+        Action::Cancel,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::SelectPrevious => "Select previous",
+            Action::SelectNext => "Select next",
+            Action::CycleNext => "Cycle to next object",
+            Action::CyclePrevious => "Cycle to previous object",
+            Action::Rename => "Rename",
+            Action::Save => "Save scene",
+            Action::SaveAs => "Save scene as…",
+            Action::SaveCopy => "Save a copy as…",
+            Action::Open => "Open scene…",
+            Action::AddObject => "Add object",
+            Action::ImportSheet => "Import sprite sheet…",
+            Action::ExportPrefab => "Export selection as prefab…",
+            Action::InsertPrefab => "Insert prefab…",
+            Action::ExportManifest => "Export asset manifest…",
+            Action::ImportCsv => "Import objects from CSV…",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::Delete => "Delete selected",
+            Action::Group => "Group selection",
+            Action::Ungroup => "Ungroup",
+            Action::FocusSelected => "Focus selected",
+            Action::FrameSelected => "Frame selected",
+            Action::FrameAll => "Frame all objects",
+            Action::ActualSize => "Actual size (100%)",
+            Action::Duplicate => "Duplicate",
+            Action::DuplicateInPlace => "Duplicate in place",
+            Action::Copy => "Copy selection",
+            Action::Paste => "Paste at cursor (centered on the pointer)",
+            Action::PasteInPlace => "Paste in place (original coordinates)",
+            Action::Solo => "Solo selected",
+            Action::MirrorHorizontal => "Mirror selection horizontally",
+            Action::MirrorVertical => "Mirror selection vertically",
+            Action::Validate => "Toggle validation panel",
+            Action::History => "Toggle history panel",
+            Action::ToggleHierarchy => "Toggle hierarchy panel",
+            Action::ToggleInspector => "Toggle inspector panel",
+            Action::ToggleGrid => "Toggle grid",
+            Action::ToggleSnap => "Toggle snap to grid",
+            Action::Preferences => "Toggle preferences",
+            Action::ShortcutHelp => "Keyboard shortcuts",
+            Action::CommandPalette => "Command palette",
+            Action::ZoomIn => "Zoom in",
+            Action::ZoomOut => "Zoom out",
+            Action::PanUp => "Pan up",
+            Action::PanDown => "Pan down",
+            Action::PanLeft => "Pan left",
+            Action::PanRight => "Pan right",
+            Action::Cancel => "Cancel",
+        }
+    }
+
+    fn default_shortcuts(self) -> Vec<KeyboardShortcut> {
+        let key = |key| KeyboardShortcut::new(Modifiers::NONE, key);
+        match self {
+            Action::SelectPrevious => vec![key(Key::ArrowUp)],
+            Action::SelectNext => vec![key(Key::ArrowDown)],
+            Action::CycleNext => vec![key(Key::Tab)],
+            Action::CyclePrevious => vec![KeyboardShortcut::new(Modifiers::SHIFT, Key::Tab)],
+            Action::Rename => vec![key(Key::F2), key(Key::Enter)],
+            Action::Save => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::S)],
+            Action::SaveAs => vec![KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::S)],
+            Action::Open => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::O)],
+            Action::CommandPalette => vec![KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::P)],
+            // ?는 자판에 따라 Shift와 함께 들어오기도 함
+            Action::ShortcutHelp => vec![
+                key(Key::F1),
+                key(Key::Questionmark),
+                KeyboardShortcut::new(Modifiers::SHIFT, Key::Questionmark),
+            ],
+            Action::Undo => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::Z)],
+            Action::Redo => vec![
+                KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z),
+                KeyboardShortcut::new(Modifiers::COMMAND, Key::Y),
+            ],
+            Action::Delete => vec![key(Key::Delete)],
+            Action::Group => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::G)],
+            Action::Ungroup => vec![KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::G)],
+            Action::FocusSelected => vec![key(Key::Period)],
+            Action::FrameSelected => vec![key(Key::F)],
+            Action::FrameAll => vec![key(Key::Home)],
+            Action::ActualSize => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::Num1)],
+            Action::Duplicate => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::D)],
+            Action::DuplicateInPlace => vec![KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::D)],
+            Action::Copy => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::C)],
+            Action::Paste => vec![KeyboardShortcut::new(Modifiers::COMMAND, Key::V)],
+            Action::PasteInPlace => vec![KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::V)],
+            Action::Solo => vec![key(Key::Slash)],
+            Action::ToggleHierarchy => vec![key(Key::H)],
+            Action::ToggleInspector => vec![key(Key::I)],
+            Action::ToggleGrid => vec![key(Key::G)],
+            Action::ToggleSnap => vec![KeyboardShortcut::new(Modifiers::SHIFT, Key::G)],
+            Action::ZoomIn => vec![key(Key::Equals), key(Key::Plus), KeyboardShortcut::new(Modifiers::SHIFT, Key::Plus)],
+            Action::ZoomOut => vec![key(Key::Minus)],
+            Action::PanUp => vec![key(Key::W)],
+            Action::PanDown => vec![key(Key::S)],
+            Action::PanLeft => vec![key(Key::A)],
+            Action::PanRight => vec![key(Key::D)],
+            Action::Cancel => vec![key(Key::Escape)],
+            // 기본 단축키 없이 명령 팔레트나 버튼으로 실행
+            Action::SaveCopy
+            | Action::AddObject
+            | Action::ImportSheet
+            | Action::ExportPrefab
+            | Action::InsertPrefab
+            | Action::ExportManifest
+            | Action::ImportCsv
+            | Action::MirrorHorizontal
+            | Action::MirrorVertical
+            | Action::Validate
+            | Action::History
+            | Action::Preferences => Vec::new(),
+        }
+    }
+
+    // 단축키 목록에서 묶어 보여줄 분류
+    pub fn category(self) -> &'static str {
+        match self {
+            Action::Save
+            | Action::SaveAs
+            | Action::SaveCopy
+            | Action::Open
+            | Action::ImportSheet
+            | Action::ExportPrefab
+            | Action::InsertPrefab
+            | Action::ExportManifest
+            | Action::ImportCsv => "File",
+            Action::Rename
+            | Action::AddObject
+            | Action::Undo
+            | Action::Redo
+            | Action::Delete
+            | Action::Group
+            | Action::Ungroup
+            | Action::Duplicate
+            | Action::DuplicateInPlace
+            | Action::Copy
+            | Action::Paste
+            | Action::PasteInPlace
+            | Action::MirrorHorizontal
+            | Action::MirrorVertical
+            | Action::Cancel => "Edit",
+            Action::SelectPrevious | Action::SelectNext | Action::CycleNext | Action::CyclePrevious => "Selection",
+            Action::FocusSelected
+            | Action::FrameSelected
+            | Action::FrameAll
+            | Action::ActualSize
+            | Action::Solo
+            | Action::ToggleGrid
+            | Action::ToggleSnap
+            | Action::ZoomIn
+            | Action::ZoomOut
+            | Action::PanUp
+            | Action::PanDown
+            | Action::PanLeft
+            | Action::PanRight => "View",
+            Action::Validate
+            | Action::History
+            | Action::ToggleHierarchy
+            | Action::ToggleInspector
+            | Action::Preferences
+            | Action::ShortcutHelp
+            | Action::CommandPalette => "Panels",
+        }
+    }
+
+    // 한 번 눌러 실행하는 명령인지 (이동, 줌, 선택 이동처럼 누르고 있는 동작은 제외)
+    pub fn is_command(self) -> bool {
+        !matches!(
+            self,
+            Action::SelectPrevious
+                | Action::SelectNext
+                | Action::CycleNext
+                | Action::CyclePrevious
+                | Action::ZoomIn
+                | Action::ZoomOut
+                | Action::PanUp
+                | Action::PanDown
+                | Action::PanLeft
+                | Action::PanRight
+                | Action::Cancel
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Vec<KeyboardShortcut>>,
+    #[serde(skip)]
+    recording: Option<(Action, Option<usize>)>,
+}
+
+impl Keymap {
+    // 사용자가 바꾸지 않은 동작은 기본 단축키를 씀
+    pub fn shortcuts(&self, action: Action) -> Vec<KeyboardShortcut> {
+        self.bindings.get(&action).cloned().unwrap_or_else(|| action.default_shortcuts())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn pressed(&self, input: &egui::InputState, action: Action) -> bool {
+        self.shortcuts(action).iter().any(|s| {
+            input.modifiers.matches_exact(s.modifiers) && input.key_pressed(s.logical_key)
+        })
+    }
+
+    // 원시 키 이벤트가 해당 동작의 단축키인지
+    pub fn matches_event(&self, event: &egui::Event, action: Action) -> bool {
+        let egui::Event::Key { key, pressed: true, modifiers, .. } = event else {
+            return false;
+        };
+        self.shortcuts(action).iter().any(|s| s.logical_key == *key && modifiers.matches_exact(s.modifiers))
+    }
+
+    pub fn down(&self, input: &egui::InputState, action: Action) -> bool {
+        self.shortcuts(action).iter().any(|s| {
+            input.modifiers.matches_exact(s.modifiers) && input.key_down(s.logical_key)
+        })
+    }
+
+    // 지금 쓰는 단축키를 분류별로 (바꾼 단축키 반영, 단축키 없는 동작은 뺌)
+    pub fn reference_ui(&self, ui: &mut egui::Ui) {
+        for category in ["File", "Edit", "Selection", "View", "Panels"] {
+            let actions: Vec<(Action, Vec<KeyboardShortcut>)> = Action::ALL.iter()
+                .filter(|a| a.category() == category)
+                .map(|&a| (a, self.shortcuts(a)))
+                .filter(|(_, shortcuts)| !shortcuts.is_empty())
+                .collect();
+            if actions.is_empty() {
+                continue;
+            }
+            ui.strong(category);
+            egui::Grid::new(("shortcut_reference", category)).num_columns(2).striped(true).show(ui, |ui| {
+                for (action, shortcuts) in actions {
+                    ui.label(action.label());
+                    let keys: Vec<String> = shortcuts.iter().map(|s| ui.ctx().format_shortcut(s)).collect();
+                    ui.monospace(keys.join("  "));
+                    ui.end_row();
+                }
+            });
+            ui.add_space(6.0);
+        }
+        let mac = ui.ctx().os() == egui::os::OperatingSystem::Mac;
+        let names = if mac { egui::ModifierNames::SYMBOLS } else { egui::ModifierNames::NAMES };
+        ui.strong("Mouse");
+        egui::Grid::new("shortcut_reference_mouse").num_columns(2).striped(true).show(ui, |ui| {
+            for (modifiers, gesture, label) in MOUSE_GESTURES {
+                ui.label(*label);
+                ui.monospace(format!("{}{}", names.format(modifiers, mac), gesture));
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.record(ui);
+
+        egui::Grid::new("keymap").num_columns(2).show(ui, |ui| {
+            for &action in Action::ALL {
+                ui.label(action.label());
+                ui.horizontal(|ui| {
+                    let mut shortcuts = self.shortcuts(action);
+                    let mut changed = false;
+                    let mut remove = None;
+                    for (i, shortcut) in shortcuts.iter().enumerate() {
+                        let recording = self.recording == Some((action, Some(i)));
+                        let text = if recording { "press a key…".to_string() } else { ui.ctx().format_shortcut(shortcut) };
+                        let button = ui.small_button(text).on_hover_text("Click to rebind, right-click to remove");
+                        if button.clicked() {
+                            self.recording = Some((action, Some(i)));
+                        }
+                        if button.secondary_clicked() {
+                            remove = Some(i);
+                        }
+                    }
+                    if let Some(i) = remove {
+                        shortcuts.remove(i);
+                        changed = true;
+                    }
+                    let adding = self.recording == Some((action, None));
+                    if ui.small_button(if adding { "press a key…" } else { "+" }).clicked() {
+                        self.recording = Some((action, None));
+                    }
+                    if changed {
+                        self.bindings.insert(action, shortcuts);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+        if ui.button("Reset Shortcuts").clicked() {
+            self.bindings.clear();
+            self.recording = None;
+        }
+    }
+
+    // 기록 중이면 다음 키 입력을 단축키로 저장 (Esc는 취소)
+    fn record(&mut self, ui: &egui::Ui) {
+        let Some((action, slot)) = self.recording else {
+            return;
+        };
+        let pressed = ui.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            })
+        });
+        let Some((key, modifiers)) = pressed else {
+            return;
+        };
+        self.recording = None;
+        if key == Key::Escape {
+            return;
+        }
+        let shortcut = KeyboardShortcut::new(modifiers, key);
+        let mut shortcuts = self.shortcuts(action);
+        match slot {
+            Some(i) if i < shortcuts.len() => shortcuts[i] = shortcut,
+            _ => shortcuts.push(shortcut),
+        }
+        self.bindings.insert(action, shortcuts);
+    }
+}
@@ -0,0 +1,29 @@
+use eframe::egui;
+use crate::model::{Layer, Scene};
+
+pub fn layers_ui(ui: &mut egui::Ui, scene: &mut Scene) {
+    let mut remove = None;
+    egui::Grid::new("layers").num_columns(4).show(ui, |ui| {
+        for layer in &mut scene.layers {
+            ui.add(egui::TextEdit::singleline(&mut layer.name).desired_width(100.0));
+            ui.checkbox(&mut layer.visible, "👁").on_hover_text("Visible");
+            ui.checkbox(&mut layer.locked, "🔒").on_hover_text("Locked");
+            if ui.small_button("✖").clicked() {
+                remove = Some(layer.id);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(id) = remove {
+        scene.remove_layer(id);
+    }
+    if ui.button("Add Layer").clicked() {
+        let id = scene.layers.iter().map(|l| l.id + 1).max().unwrap_or(0);
+        scene.layers.push(Layer {
+            id,
+            name: format!("Layer {}", scene.layers.len() + 1),
+            visible: true,
+            locked: false,
+        });
+    }
+}
@@ -0,0 +1,37 @@
+// 씬 데이터 모델과 저장 형식은 GUI 없이도 쓸 수 있음 (default-features = false)
+pub mod model;
+pub mod save;
+pub mod transform;
+
+#[cfg(feature = "gui")]
+mod diff;
+#[cfg(feature = "gui")]
+pub mod editor;
+#[cfg(feature = "gui")]
+mod history;
+#[cfg(feature = "gui")]
+mod keymap;
+#[cfg(feature = "gui")]
+mod layers;
+#[cfg(feature = "gui")]
+mod loader;
+#[cfg(feature = "gui")]
+mod palette;
+#[cfg(feature = "gui")]
+mod path_prompt;
+#[cfg(feature = "gui")]
+mod prefs;
+#[cfg(feature = "gui")]
+pub mod recovery;
+#[cfg(feature = "gui")]
+mod relink;
+#[cfg(feature = "gui")]
+mod sheet;
+#[cfg(feature = "gui")]
+mod thumbnail;
+#[cfg(feature = "gui")]
+mod toast;
+#[cfg(feature = "gui")]
+mod validation;
+#[cfg(feature = "gui")]
+mod widgets;
@@ -0,0 +1,308 @@
+use eframe::egui;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use crate::model::Scene;
+use crate::save;
+
+// 동시에 디코딩하는 이미지 수
+const MAX_DECODES: usize = 4;
+const CHUNK: usize = 64 * 1024;
+
+// 큰 씬 파일을 백그라운드에서 읽어 UI가 멈추지 않게 함
+pub struct SceneLoad {
+    pub path: String,
+    total: u64,
+    read: Arc<AtomicU64>,
+    // 읽은 뒤에는 파싱한 오브젝트 수와 파일 내용으로 어림한 전체 수
+    parsed: Arc<AtomicUsize>,
+    expected: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    result: Receiver<Result<Scene, String>>,
+}
+
+impl SceneLoad {
+    pub fn start(path: &str) -> Self {
+        let total = std::fs::metadata(path).map_or(0, |m| m.len());
+        let read = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (parsed, expected) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let (tx, result) = mpsc::channel();
+        let (thread_path, thread_read, thread_cancel) = (path.to_string(), read.clone(), cancel.clone());
+        let (thread_parsed, thread_expected) = (parsed.clone(), expected.clone());
+        std::thread::spawn(move || {
+            // 취소되면 결과를 보내지 않고 끝냄
+            if let Some(content) = read_chunked(&thread_path, &thread_read, &thread_cancel) {
+                let scene = content.and_then(|content| match content {
+                    Some(content) => {
+                        thread_expected.store(save::estimate_objects(&content), Ordering::Relaxed);
+                        save::parse_scene_with_progress(&content, &thread_parsed, &thread_cancel)
+                    }
+                    None => Ok(Scene::default()),
+                });
+                if !thread_cancel.load(Ordering::Relaxed) {
+                    let _ = tx.send(scene);
+                }
+            }
+        });
+        Self { path: path.to_string(), total, read, parsed, expected, cancel, result }
+    }
+
+    pub fn poll(&self) -> Option<Result<Scene, String>> {
+        self.result.try_recv().ok()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    // 다 읽은 뒤에는 파싱한 오브젝트 수
+    pub fn progress(&self) -> (f32, String) {
+        let read = self.read.load(Ordering::Relaxed);
+        if self.total == 0 || read >= self.total {
+            let (parsed, expected) = (self.parsed.load(Ordering::Relaxed), self.expected.load(Ordering::Relaxed));
+            if expected == 0 {
+                return (1.0, "Parsing…".to_string());
+            }
+            let fraction = (parsed as f32 / expected as f32).min(1.0);
+            return (fraction, format!("Parsed {} / {} objects", parsed.min(expected), expected));
+        }
+        let kb = |bytes: u64| bytes as f32 / 1024.0;
+        (read as f32 / self.total as f32, format!("Reading {:.0} / {:.0} KB", kb(read), kb(self.total)))
+    }
+}
+
+// 취소되면 None, 없는 파일은 Ok(None) (load_scene처럼 빈 씬으로)
+fn read_chunked(path: &str, read: &AtomicU64, cancel: &AtomicBool) -> Option<Result<Option<String>, String>> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Some(Ok(None)),
+        Err(e) => return Some(Err(format!("Could not read {}: {}", path, e))),
+    };
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0; CHUNK];
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                bytes.extend_from_slice(&chunk[..n]);
+                read.fetch_add(n as u64, Ordering::Relaxed);
+            }
+            Err(e) => return Some(Err(format!("Could not read {}: {}", path, e))),
+        }
+    }
+    Some(String::from_utf8(bytes).map(Some).map_err(|_| format!("{} is not valid UTF-8 text", path)))
+}
+
+// SVG 래스터 한 변의 한계 (픽셀)
+pub const SVG_MIN_SIZE: u32 = 16;
+pub const SVG_MAX_SIZE: u32 = 4096;
+
+// 확장자가 틀리거나 없어도 (예: .png로 저장된 WebP) 파일 내용으로 형식을 알아냄
+pub fn open_image(path: &str) -> Option<image::DynamicImage> {
+    open_image_sized(path, None).map(|(image, _)| image)
+}
+
+// SVG는 긴 변을 svg_size 픽셀로 (None이면 문서 크기대로) 그림. 벡터 이미지였으면 그린 긴 변
+pub fn open_image_sized(path: &str, svg_size: Option<u32>) -> Option<(image::DynamicImage, Option<u32>)> {
+    let bytes = std::fs::read(path).ok()?;
+    if is_svg(&bytes) {
+        let image = rasterize_svg(path, &bytes, svg_size)?;
+        let side = image.width().max(image.height());
+        return Some((image, Some(side)));
+    }
+    let image = image::io::Reader::new(std::io::Cursor::new(bytes)).with_guessed_format().ok()?.decode().ok()?;
+    Some((image, None))
+}
+
+// 확장자 대신 앞부분에 <svg 태그가 있는지로 판단 (XML 선언이나 주석이 앞에 올 수 있음)
+fn is_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    text.starts_with('<') && text.contains("<svg")
+}
+
+#[cfg(feature = "svg")]
+fn rasterize_svg(path: &str, bytes: &[u8], size: Option<u32>) -> Option<image::DynamicImage> {
+    use resvg::{tiny_skia, usvg};
+    use std::sync::OnceLock;
+
+    // 시스템 글꼴은 한 번만 읽어 두고 같이 씀
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    let fontdb = FONTS.get_or_init(|| {
+        let mut db = usvg::fontdb::Database::new();
+        db.load_system_fonts();
+        Arc::new(db)
+    });
+    // SVG 안의 상대 경로 이미지는 파일 옆에서 찾음
+    let options = usvg::Options {
+        resources_dir: std::path::Path::new(path).parent().map(|p| p.to_path_buf()),
+        fontdb: fontdb.clone(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_data(bytes, &options).ok()?;
+    let doc = tree.size();
+    let scale = size.map_or(1.0, |s| s as f32 / doc.width().max(doc.height()));
+    let side = |v: f32| ((v * scale).round() as u32).clamp(1, SVG_MAX_SIZE);
+    let (w, h) = (side(doc.width()), side(doc.height()));
+    let mut pixmap = tiny_skia::Pixmap::new(w, h)?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(w as f32 / doc.width(), h as f32 / doc.height()), &mut pixmap.as_mut());
+    // tiny-skia는 알파를 미리 곱해 두므로 되돌림
+    let rgba: Vec<u8> = pixmap.pixels().iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    image::RgbaImage::from_raw(w, h, rgba).map(image::DynamicImage::ImageRgba8)
+}
+
+// svg 기능 없이 빌드하면 SVG는 불러오지 못한 이미지로 처리
+#[cfg(not(feature = "svg"))]
+fn rasterize_svg(_path: &str, _bytes: &[u8], _size: Option<u32>) -> Option<image::DynamicImage> {
+    None
+}
+
+// max_bytes보다 큰 이미지는 비율을 유지한 채 줄임
+pub fn decode_image(path: &str, max_bytes: Option<usize>) -> Option<egui::ColorImage> {
+    decode_image_sized(path, max_bytes, None).map(|(image, _)| image)
+}
+
+fn decode_image_sized(path: &str, max_bytes: Option<usize>, svg_size: Option<u32>) -> Option<(egui::ColorImage, Option<u32>)> {
+    let (mut img, vector) = open_image_sized(path, svg_size)?;
+    let bytes = img.width() as usize * img.height() as usize * 4;
+    if let Some(max) = max_bytes.filter(|&max| bytes > max) {
+        let factor = (max as f64 / bytes as f64).sqrt();
+        let (w, h) = ((img.width() as f64 * factor) as u32, (img.height() as f64 * factor) as u32);
+        img = img.resize(w.max(1), h.max(1), image::imageops::FilterType::Triangle);
+    }
+    let size = [img.width() as usize, img.height() as usize];
+    let rgba = img.to_rgba8();
+    Some((egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice()), vector))
+}
+
+// 화면에 들어온 오브젝트의 이미지만 백그라운드에서 디코딩
+// 디코딩 결과: 경로, 이미지, SVG면 래스터화한 긴 변 (예산 때문에 줄이기 전)
+type Decoded = (String, Option<egui::ColorImage>, Option<u32>);
+
+pub struct TextureLoader {
+    // SVG는 원하는 래스터 크기를 함께
+    queue: VecDeque<(String, Option<u32>)>,
+    queued: HashSet<String>,
+    in_flight: usize,
+    // 줌에 따라 다시 래스터화할 수 있는 경로와 마지막으로 그린 긴 변
+    vector: HashMap<String, u32>,
+    pub max_bytes: Option<usize>,
+    pub options: egui::TextureOptions,
+    tx: Sender<Decoded>,
+    rx: Receiver<Decoded>,
+}
+
+impl Default for TextureLoader {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            queue: VecDeque::new(),
+            queued: HashSet::new(),
+            in_flight: 0,
+            vector: HashMap::new(),
+            max_bytes: None,
+            options: egui::TextureOptions::LINEAR,
+            tx,
+            rx,
+        }
+    }
+}
+
+impl TextureLoader {
+    pub fn request(&mut self, path: &str) {
+        self.request_sized(path, None);
+    }
+
+    // 이미 불러온 SVG를 긴 변 size 픽셀로 다시 그림
+    pub fn request_sized(&mut self, path: &str, size: Option<u32>) {
+        if !path.is_empty() && self.queued.insert(path.to_string()) {
+            self.queue.push_back((path.to_string(), size));
+        }
+    }
+
+    pub fn svg_size(&self, path: &str) -> Option<u32> {
+        self.vector.get(path).copied()
+    }
+
+    pub fn is_loading(&self, path: &str) -> bool {
+        self.queued.contains(path)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.queued.len()
+    }
+
+    // 새 씬을 열면 아직 시작하지 않은 요청은 버림
+    pub fn clear_queue(&mut self) {
+        for (path, _) in self.queue.drain(..) {
+            self.queued.remove(&path);
+        }
+    }
+
+    // 끝난 디코딩을 텍스처로 올리고 대기 중인 요청을 시작. 실패한 경로는 None
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<(String, Option<egui::TextureHandle>)> {
+        let mut done = Vec::new();
+        while let Ok((path, image, vector)) = self.rx.try_recv() {
+            self.in_flight -= 1;
+            self.queued.remove(&path);
+            if let Some(side) = vector {
+                self.vector.insert(path.clone(), side);
+            }
+            let tex = image.map(|image| ctx.load_texture(path.clone(), image, self.options));
+            done.push((path, tex));
+        }
+        while self.in_flight < MAX_DECODES {
+            let Some((path, svg_size)) = self.queue.pop_front() else {
+                break;
+            };
+            self.in_flight += 1;
+            let (tx, ctx, max_bytes) = (self.tx.clone(), ctx.clone(), self.max_bytes);
+            std::thread::spawn(move || {
+                let (image, vector) = match decode_image_sized(&path, max_bytes, svg_size) {
+                    Some((image, vector)) => (Some(image), vector),
+                    None => (None, None),
+                };
+                let _ = tx.send((path, image, vector));
+                ctx.request_repaint();
+            });
+        }
+        done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10" fill="red"/></svg>"#;
+
+    #[test]
+    fn detects_svg_by_content() {
+        assert!(is_svg(SVG.as_bytes()));
+        assert!(is_svg(b"\xef\xbb\xbf  <svg></svg>"));
+        assert!(!is_svg(b"\x89PNG\r\n\x1a\n<svg"));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn rasterizes_svg_at_requested_size() {
+        let image = rasterize_svg("", SVG.as_bytes(), Some(64)).unwrap();
+        assert_eq!((image.width(), image.height()), (64, 32));
+        assert_eq!(image.to_rgba8().get_pixel(10, 10).0, [255, 0, 0, 255]);
+        let native = rasterize_svg("", SVG.as_bytes(), None).unwrap();
+        assert_eq!((native.width(), native.height()), (20, 10));
+    }
+}
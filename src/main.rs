@@ -1,13 +1,12 @@
-use crate::editor::StarEditor;
-
-mod editor;
-mod save;
+use stareditor::editor::StarEditor;
+use stareditor::recovery;
 
 fn main() -> Result<(), eframe::Error> {
+    recovery::install_panic_hook();
     let options = eframe::NativeOptions::default();
     eframe::run_native(
         "Star Editor",
         options,
-        Box::new(|_cc| Box::new(StarEditor::default())),
+        Box::new(|cc| Box::new(StarEditor::new(cc))),
     )
-}
\ No newline at end of file
+}
@@ -0,0 +1,508 @@
+use emath::{pos2, Rect};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::save;
+use crate::transform::Transform;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameObject {
+    pub id: usize,
+    pub name: String,
+    pub position: [f32; 2],
+    pub rotation: f32,
+    pub scale: [f32; 2],
+    pub image_path: Option<String>,
+    #[serde(default = "default_tint")]
+    pub tint: [u8; 4],
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default)]
+    pub uv_rect: Option<[f32; 4]>,
+    #[serde(default)]
+    pub layer: Option<usize>,
+    #[serde(default)]
+    pub parent: Option<usize>,
+    #[serde(default = "default_pivot")]
+    pub pivot: [f32; 2],
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub flip_x: bool,
+    #[serde(default)]
+    pub flip_y: bool,
+    #[serde(default = "default_snap")]
+    pub snap: bool,
+    // 다른 오브젝트 위치에서 anchor_offset만큼 떨어진 자리를 계속 따라감
+    #[serde(default)]
+    pub anchor_to: Option<usize>,
+    #[serde(default)]
+    pub anchor_offset: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Scene {
+    #[serde(deserialize_with = "save::deserialize_objects")]
+    pub objects: Vec<GameObject>,
+    #[serde(default = "default_background")]
+    pub background_color: [u8; 4],
+    #[serde(default)]
+    pub layers: Vec<Layer>,
+    #[serde(default = "default_pixels_per_unit")]
+    pub pixels_per_unit: f32,
+    #[serde(default)]
+    pub session: Session,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    // 저장한 프로그램의 파일 형식 버전 (버전이 생기기 전 파일은 0)
+    #[serde(default)]
+    pub version: u32,
+    // id순으로 정렬해 저장한 파일의 그리기 순서 (id 목록). 읽은 뒤에는 비워 둠
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub draw_order: Vec<usize>,
+}
+
+// 이름 붙여 저장한 카메라 위치 (화면 가운데의 월드 좌표와 배율)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub name: String,
+    pub center: [f32; 2],
+    pub zoom: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Layer {
+    pub id: usize,
+    pub name: String,
+    pub visible: bool,
+    pub locked: bool,
+}
+
+// 다시 열었을 때 작업 상태를 되살리기 위한 정보 (오브젝트는 id로 기록)
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Session {
+    pub selected: Vec<usize>,
+    pub collapsed: Vec<usize>,
+    pub view: Option<([f32; 2], f32)>,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self {
+            objects: Vec::new(),
+            background_color: default_background(),
+            layers: Vec::new(),
+            pixels_per_unit: default_pixels_per_unit(),
+            session: Session::default(),
+            bookmarks: Vec::new(),
+            version: save::SCENE_VERSION,
+            draw_order: Vec::new(),
+        }
+    }
+}
+
+impl Scene {
+    pub fn next_id(&self) -> usize {
+        self.objects.iter().map(|o| o.id + 1).max().unwrap_or(0)
+    }
+
+    fn layer(&self, obj: &GameObject) -> Option<&Layer> {
+        obj.layer.and_then(|id| self.layers.iter().find(|l| l.id == id))
+    }
+
+    pub fn is_visible(&self, obj: &GameObject) -> bool {
+        self.layer(obj).is_none_or(|l| l.visible)
+    }
+
+    // 숨겨진 레이어도 선택할 수 없음
+    pub fn is_locked(&self, obj: &GameObject) -> bool {
+        self.layer(obj).is_some_and(|l| l.locked || !l.visible)
+    }
+
+    pub fn remove_layer(&mut self, id: usize) {
+        self.layers.retain(|l| l.id != id);
+        for obj in &mut self.objects {
+            if obj.layer == Some(id) {
+                obj.layer = None;
+            }
+        }
+    }
+
+    // 모든 오브젝트를 감싸는 월드 좌표 사각형
+    pub fn bounds(&self) -> Option<Rect> {
+        self.world_transforms().iter().zip(&self.objects)
+            .map(|(w, o)| w.centered(o.pivot).aabb())
+            .reduce(|a, b| a.union(b))
+    }
+
+    // 메모리 순서와 상관없이 파일 내용이 같도록 id순으로 놓고 그리기 순서는 따로 기록
+    pub fn sort_by_id(&mut self) {
+        self.draw_order = self.objects.iter().map(|o| o.id).collect();
+        self.objects.sort_by_key(|o| o.id);
+    }
+
+    // 예전 버전이 저장한 빈 경로는 이미지 없음으로
+    pub fn drop_empty_image_paths(&mut self) {
+        for obj in &mut self.objects {
+            let path = obj.image_path.take();
+            obj.set_image_path(path);
+        }
+    }
+
+    // sort_by_id의 역. 목록에 없는 오브젝트는 원래 순서대로 뒤에
+    pub fn apply_draw_order(&mut self) {
+        let order: std::collections::HashMap<usize, usize> =
+            self.draw_order.drain(..).enumerate().map(|(n, id)| (id, n)).collect();
+        if !order.is_empty() {
+            self.objects.sort_by_key(|o| order.get(&o.id).copied().unwrap_or(usize::MAX));
+        }
+    }
+
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.objects.iter().position(|o| o.id == id)
+    }
+
+    // 부모를 따라가며 모든 오브젝트의 월드 변환 계산
+    pub fn world_transforms(&self) -> Vec<Transform> {
+        let index: std::collections::HashMap<usize, usize> =
+            self.objects.iter().enumerate().map(|(i, o)| (o.id, i)).collect();
+        let mut worlds = vec![None; self.objects.len()];
+        for i in 0..self.objects.len() {
+            self.resolve_world(i, &index, &mut worlds, 0);
+        }
+        worlds.into_iter().map(|w| w.unwrap_or_default()).collect()
+    }
+
+    fn resolve_world(
+        &self,
+        i: usize,
+        index: &std::collections::HashMap<usize, usize>,
+        worlds: &mut Vec<Option<Transform>>,
+        depth: usize,
+    ) -> Transform {
+        if let Some(world) = worlds[i] {
+            return world;
+        }
+        let local = self.objects[i].local_transform();
+        // 순환 참조는 깊이 제한으로 끊음
+        let mut world = match self.objects[i].parent.and_then(|p| index.get(&p)) {
+            Some(&p) if depth < self.objects.len() => self.resolve_world(p, index, worlds, depth + 1).apply(local),
+            _ => local,
+        };
+        // 기준 오브젝트가 있으면 위치만 그쪽을 따름 (회전/스케일은 부모 기준 그대로)
+        if let Some(&a) = self.objects[i].anchor_to.and_then(|a| index.get(&a)).filter(|_| depth < self.objects.len()) {
+            let anchor = self.resolve_world(a, index, worlds, depth + 1).position;
+            let offset = self.objects[i].anchor_offset;
+            world.position = [anchor[0] + offset[0], anchor[1] + offset[1]];
+        }
+        worlds[i] = Some(world);
+        world
+    }
+
+    pub fn parent_world(&self, worlds: &[Transform], i: usize) -> Transform {
+        self.objects[i]
+            .parent
+            .and_then(|p| self.index_of(p))
+            .map_or(Transform::default(), |p| worlds[p])
+    }
+
+    // 고정된 기준 오브젝트의 월드 위치 (없거나 지워졌으면 None)
+    pub fn anchor_world(&self, worlds: &[Transform], i: usize) -> Option<[f32; 2]> {
+        self.objects[i].anchor_to.and_then(|a| self.index_of(a)).map(|a| worlds[a].position)
+    }
+
+    // id의 월드 위치가 부모나 기준 오브젝트를 따라 on에 달려 있는지 (자기 자신 포함)
+    pub fn depends_on(&self, id: usize, on: usize) -> bool {
+        let mut stack = vec![id];
+        let mut visited = std::collections::HashSet::new();
+        while let Some(current) = stack.pop() {
+            if current == on {
+                return true;
+            }
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(i) = self.index_of(current) {
+                stack.extend(self.objects[i].parent);
+                stack.extend(self.objects[i].anchor_to);
+            }
+        }
+        false
+    }
+
+    // 지금 월드 위치를 유지하도록 오프셋을 계산해 고정. 순환이 생기면 거부
+    pub fn set_anchor(&mut self, id: usize, anchor: Option<usize>) -> bool {
+        if anchor.is_some_and(|a| self.depends_on(a, id)) {
+            return false;
+        }
+        let Some(i) = self.index_of(id) else {
+            return false;
+        };
+        let worlds = self.world_transforms();
+        let world = worlds[i];
+        let parent_world = self.parent_world(&worlds, i);
+        let anchor_world = anchor.and_then(|a| self.index_of(a)).map(|a| worlds[a].position);
+        let obj = &mut self.objects[i];
+        obj.anchor_to = anchor;
+        obj.set_world_position(parent_world, anchor_world, world);
+        true
+    }
+
+    // id가 ancestor 자신이거나 그 자손인지
+    pub fn is_descendant(&self, id: usize, ancestor: usize) -> bool {
+        let mut current = Some(id);
+        for _ in 0..=self.objects.len() {
+            match current {
+                Some(c) if c == ancestor => return true,
+                Some(c) => current = self.index_of(c).and_then(|i| self.objects[i].parent),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    // 월드 위치가 바뀌지 않도록 로컬 변환을 다시 계산
+    pub fn reparent(&mut self, id: usize, parent: Option<usize>) -> bool {
+        if parent.is_some_and(|p| self.depends_on(p, id)) {
+            return false;
+        }
+        let Some(i) = self.index_of(id) else {
+            return false;
+        };
+        let worlds = self.world_transforms();
+        let parent_world = parent
+            .and_then(|p| self.index_of(p))
+            .map_or(Transform::default(), |p| worlds[p]);
+        let local = parent_world.localize(worlds[i]);
+        let obj = &mut self.objects[i];
+        obj.position = local.position;
+        obj.rotation = local.rotation;
+        obj.scale = local.scale;
+        obj.parent = parent;
+        true
+    }
+
+    // 계층 트리 순서대로 (인덱스, 깊이)
+    pub fn tree_order(&self) -> Vec<(usize, usize)> {
+        let mut order = Vec::with_capacity(self.objects.len());
+        let mut visited = vec![false; self.objects.len()];
+        let roots: Vec<usize> = (0..self.objects.len())
+            .filter(|&i| self.objects[i].parent.and_then(|p| self.index_of(p)).is_none())
+            .collect();
+        for root in roots {
+            self.push_subtree(root, 0, &mut order, &mut visited);
+        }
+        // 순환 참조로 루트에 닿지 않는 오브젝트도 표시
+        for i in 0..self.objects.len() {
+            if !visited[i] {
+                self.push_subtree(i, 0, &mut order, &mut visited);
+            }
+        }
+        order
+    }
+
+    fn push_subtree(&self, i: usize, depth: usize, order: &mut Vec<(usize, usize)>, visited: &mut Vec<bool>) {
+        if visited[i] {
+            return;
+        }
+        visited[i] = true;
+        order.push((i, depth));
+        let id = self.objects[i].id;
+        for child in 0..self.objects.len() {
+            if self.objects[child].parent == Some(id) {
+                self.push_subtree(child, depth + 1, order, visited);
+            }
+        }
+    }
+
+    // 중복된 id를 새 id로 바꾸고 바꾼 개수를 반환
+    pub fn fix_duplicate_ids(&mut self) -> usize {
+        let mut next = self.next_id();
+        let mut seen = std::collections::HashSet::new();
+        let mut fixed = 0;
+        for obj in &mut self.objects {
+            if !seen.insert(obj.id) {
+                obj.id = next;
+                next += 1;
+                fixed += 1;
+            }
+        }
+        fixed
+    }
+}
+
+fn default_pixels_per_unit() -> f32 {
+    10.0
+}
+
+fn default_background() -> [u8; 4] {
+    [30, 30, 30, 255]
+}
+
+fn default_tint() -> [u8; 4] {
+    [255, 255, 255, 255]
+}
+
+// 뒤집힌 축은 uv 좌표를 맞바꿔서 그림
+fn flip_uv(uv: Rect, x: bool, y: bool) -> Rect {
+    let (mut min, mut max) = (uv.min, uv.max);
+    if x {
+        std::mem::swap(&mut min.x, &mut max.x);
+    }
+    if y {
+        std::mem::swap(&mut min.y, &mut max.y);
+    }
+    Rect::from_min_max(min, max)
+}
+
+// false면 전역 스냅이 켜져 있어도 이 오브젝트는 스냅하지 않음
+pub(crate) fn default_snap() -> bool {
+    true
+}
+
+pub(crate) fn default_pivot() -> [f32; 2] {
+    [0.5, 0.5]
+}
+
+impl GameObject {
+    pub fn uv(&self) -> Rect {
+        let [x0, y0, x1, y1] = self.uv_rect.unwrap_or([0.0, 0.0, 1.0, 1.0]);
+        flip_uv(Rect::from_min_max(pos2(x0, y0), pos2(x1, y1)), self.flip_x, self.flip_y)
+    }
+
+    // 공백으로 나뉜 검색어를 모두 만족하는지 (대소문자 무시).
+    // tag:이름, prop:키 또는 prop:키=값, 그 외에는 이름 부분 일치
+    pub fn matches_query(&self, query: &str) -> bool {
+        let contains = |text: &str, part: &str| text.to_lowercase().contains(part);
+        query.to_lowercase().split_whitespace().all(|term| {
+            if let Some(tag) = term.strip_prefix("tag:") {
+                self.tags.iter().any(|t| contains(t, tag))
+            } else if let Some(prop) = term.strip_prefix("prop:") {
+                let (key, value) = prop.split_once('=').unwrap_or((prop, ""));
+                self.properties.iter().any(|(k, v)| contains(k, key) && contains(v, value))
+            } else {
+                contains(&self.name, term)
+            }
+        })
+    }
+
+    // 아직 기본 이름("Object 5")이면 이미지 파일 이름에서 확장자를 뺀 것으로 바꿈
+    pub fn name_after_image(&mut self) {
+        if self.name != format!("Object {}", self.id) {
+            return;
+        }
+        let stem = self.image_path.as_deref()
+            .and_then(|p| std::path::Path::new(p).file_stem())
+            .map(|s| s.to_string_lossy().into_owned());
+        if let Some(stem) = stem.filter(|s| !s.is_empty()) {
+            self.name = stem;
+        }
+    }
+
+    // 빈 문자열은 "이미지 없음"으로 취급
+    pub fn set_image_path(&mut self, path: Option<String>) {
+        self.image_path = path.filter(|p| !p.is_empty());
+    }
+
+    // edited가 base와 다른 필드만 가져옴 (id는 그대로)
+    pub fn apply_changes(&mut self, base: &GameObject, edited: &GameObject) {
+        macro_rules! take {
+            ($($field:ident),*) => {
+                // 필드가 새로 생기면 여기서 컴파일이 멈춤
+                let GameObject { id: _, $($field: _),* } = edited;
+                $(
+                    if edited.$field != base.$field {
+                        self.$field = edited.$field.clone();
+                    }
+                )*
+            };
+        }
+        take!(
+            name, position, rotation, scale, image_path, tint, tags, properties, uv_rect, layer, parent, pivot, note,
+            flip_x, flip_y, snap, anchor_to, anchor_offset
+        );
+    }
+
+    // id만 새로 받고 나머지 필드는 모두 그대로 복사
+    pub fn duplicate(&self, id: usize) -> Self {
+        Self { id, ..self.clone() }
+    }
+
+    // 월드 위치가 world가 되도록 로컬 위치(기준 오브젝트가 있으면 오프셋)를 바꿈
+    pub fn set_world_position(&mut self, parent_world: Transform, anchor_world: Option<[f32; 2]>, world: Transform) {
+        match anchor_world {
+            Some(anchor) => self.anchor_offset = [world.position[0] - anchor[0], world.position[1] - anchor[1]],
+            None => self.position = parent_world.localize(world).position,
+        }
+    }
+
+    // 부모 기준 로컬 변환
+    pub fn local_transform(&self) -> Transform {
+        Transform {
+            position: self.position,
+            rotation: self.rotation,
+            scale: self.scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 필드를 빠짐없이 적어 두어 새 필드가 생기면 여기서 컴파일이 멈춤
+    fn sample() -> GameObject {
+        GameObject {
+            id: 3,
+            name: "Tree".to_string(),
+            position: [1.5, -2.0],
+            rotation: 0.75,
+            scale: [2.0, -1.0],
+            image_path: Some("tree.png".to_string()),
+            tint: [10, 20, 30, 40],
+            tags: vec!["scenery".to_string()],
+            properties: BTreeMap::from([("hp".to_string(), "5".to_string())]),
+            uv_rect: Some([0.0, 0.5, 0.5, 1.0]),
+            layer: Some(2),
+            parent: Some(1),
+            pivot: [0.25, 1.0],
+            note: "check collision".to_string(),
+            flip_x: true,
+            flip_y: true,
+            snap: false,
+            anchor_to: Some(4),
+            anchor_offset: [3.0, 4.0],
+        }
+    }
+
+    #[test]
+    fn duplicate_keeps_everything_but_id() {
+        let orig = sample();
+        let dup = orig.duplicate(9);
+        assert_eq!(dup, GameObject { id: 9, ..orig.clone() });
+        assert_ne!(dup.id, orig.id);
+    }
+
+    #[test]
+    fn apply_changes_keeps_fields_changed_elsewhere() {
+        let base = sample();
+        let mut edited = base.clone();
+        edited.name = "Oak".to_string();
+        // 인스펙터에서 편집하는 동안 씬 뷰에서 옮기고 돌림
+        let mut scene_obj = base.clone();
+        scene_obj.position = [9.0, 9.0];
+        scene_obj.rotation = 2.0;
+        scene_obj.apply_changes(&base, &edited);
+        assert_eq!(scene_obj, GameObject { name: "Oak".to_string(), position: [9.0, 9.0], rotation: 2.0, ..base });
+    }
+
+    #[test]
+    fn empty_image_path_means_no_image() {
+        let mut obj = sample();
+        obj.set_image_path(Some(String::new()));
+        assert_eq!(obj.image_path, None);
+        obj.set_image_path(Some("b.png".to_string()));
+        assert_eq!(obj.image_path.as_deref(), Some("b.png"));
+    }
+}
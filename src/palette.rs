@@ -0,0 +1,96 @@
+use eframe::egui;
+use crate::keymap::{Action, Keymap};
+
+#[derive(Default)]
+pub struct Palette {
+    open: bool,
+    query: String,
+    cursor: usize,
+    focus: bool,
+}
+
+impl Palette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.cursor = 0;
+        self.focus = self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    // 고른 명령을 돌려줌. 위/아래로 이동, Enter로 실행, Esc로 닫기
+    pub fn show(&mut self, ctx: &egui::Context, keymap: &Keymap) -> Option<Action> {
+        if !self.open {
+            return None;
+        }
+        let query = self.query.to_lowercase();
+        let matches: Vec<Action> = Action::ALL.iter().copied()
+            .filter(|a| a.is_command() && *a != Action::CommandPalette)
+            .filter(|a| a.label().to_lowercase().contains(&query))
+            .collect();
+
+        let (up, down, enter, escape) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if down {
+            self.cursor += 1;
+        }
+        if up {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        self.cursor = self.cursor.min(matches.len().saturating_sub(1));
+
+        let mut chosen = None;
+        egui::Window::new("Command Palette")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+            .fixed_size(egui::vec2(320.0, 0.0))
+            .show(ctx, |ui| {
+                let edit = ui.add(egui::TextEdit::singleline(&mut self.query).hint_text("Type a command…").desired_width(f32::INFINITY));
+                if self.focus {
+                    edit.request_focus();
+                    self.focus = false;
+                }
+                if edit.changed() {
+                    self.cursor = 0;
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (n, &action) in matches.iter().enumerate() {
+                        let shortcut = keymap.shortcuts(action).first().map(|s| ctx.format_shortcut(s)).unwrap_or_default();
+                        let row = ui.horizontal(|ui| {
+                            let row = ui.selectable_label(n == self.cursor, action.label());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| ui.weak(shortcut));
+                            row
+                        }).inner;
+                        if n == self.cursor && (up || down) {
+                            row.scroll_to_me(None);
+                        }
+                        if row.clicked() {
+                            chosen = Some(action);
+                        }
+                    }
+                    if matches.is_empty() {
+                        ui.weak("No matching commands");
+                    }
+                });
+            });
+
+        if enter {
+            chosen = chosen.or(matches.get(self.cursor).copied());
+        }
+        if chosen.is_some() || escape {
+            self.open = false;
+        }
+        chosen
+    }
+}
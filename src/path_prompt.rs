@@ -0,0 +1,86 @@
+use eframe::egui;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PathAction {
+    Open,
+    SaveAs,
+    SaveCopy,
+    ExportPrefab,
+    InsertPrefab,
+    ExportManifest,
+    ImportCsv,
+    SetImage,
+}
+
+impl PathAction {
+    fn title(self) -> &'static str {
+        match self {
+            PathAction::Open => "Open Scene",
+            PathAction::SaveAs => "Save Scene As",
+            PathAction::SaveCopy => "Save a Copy As",
+            PathAction::ExportPrefab => "Export Selection as Prefab",
+            PathAction::InsertPrefab => "Insert Prefab",
+            PathAction::ExportManifest => "Export Asset Manifest",
+            PathAction::ImportCsv => "Import Objects from CSV",
+            PathAction::SetImage => "Set Image",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PathPrompt {
+    action: Option<PathAction>,
+    path: String,
+    // 열기 창에서 보여줄 캐시된 썸네일 (어느 경로의 것인지와 함께)
+    preview: Option<(String, Option<egui::TextureHandle>)>,
+}
+
+impl PathPrompt {
+    pub fn open(&mut self, action: PathAction, path: &str) {
+        self.action = Some(action);
+        self.path = path.to_string();
+    }
+
+    // 확인을 누르면 (동작, 경로)를 돌려줌
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<(PathAction, String)> {
+        let action = self.action?;
+        let mut result = None;
+        let mut open = true;
+        egui::Window::new(action.title())
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let edit = ui.text_edit_singleline(&mut self.path);
+                if action == PathAction::Open {
+                    self.preview_ui(ui);
+                }
+                let submit = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.horizontal(|ui| {
+                    if (ui.button("OK").clicked() || submit) && !self.path.trim().is_empty() {
+                        result = Some((action, self.path.trim().to_string()));
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.action = None;
+                    }
+                });
+            });
+        if !open || result.is_some() {
+            self.action = None;
+        }
+        result
+    }
+
+    fn preview_ui(&mut self, ui: &mut egui::Ui) {
+        let path = self.path.trim().to_string();
+        if self.preview.as_ref().is_none_or(|(p, _)| *p != path) {
+            let tex = crate::thumbnail::cached_thumbnail(&path)
+                .and_then(|thumb| crate::loader::decode_image(&thumb.to_string_lossy(), None))
+                .map(|image| ui.ctx().load_texture(format!("thumbnail:{}", path), image, Default::default()));
+            self.preview = Some((path, tex));
+        }
+        if let Some((_, Some(tex))) = &self.preview {
+            ui.image((tex.id(), tex.size_vec2()));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::keymap::Keymap;
+use crate::widgets;
+
+pub const PREFS_KEY: &str = "preferences";
+pub const SETTINGS_KEY: &str = "editor_settings";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Preferences {
+    pub pan_speed: f32,
+    pub zoom_speed: f32,
+    pub invert_zoom: bool,
+    pub object_template: ObjectTemplate,
+    pub selected_color: [u8; 4],
+    pub outline_color: [u8; 4],
+    pub antialiasing: bool,
+    pub staged_edits: bool,
+    pub remember_session: bool,
+    pub negative_scale: bool,
+    pub show_fps: bool,
+    // 좁은 화면에서 접어 둔 사이드 패널 상태도 함께 저장
+    pub show_hierarchy: bool,
+    pub show_inspector: bool,
+    // 0이면 제한 없음
+    pub texture_budget_mb: u32,
+    pub pixel_art: bool,
+    pub smooth_minify: bool,
+    pub auto_name: bool,
+    pub sort_on_save: bool,
+    // 화면에 맞출 때 한쪽 가장자리에 남기는 여백 (화면 크기의 %)
+    pub frame_margin: f32,
+    pub keymap: Keymap,
+}
+
+// 툴바에서 바꾸는 격자/스냅/표시 상태. 설정 창 없이 마지막 상태를 그대로 저장
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub show_grid: bool,
+    pub grid_size: f32,
+    // 격자 한 칸을 나누는 보조선 수 (1이면 없음). 스냅은 보조선에 맞춤
+    pub grid_subdivisions: u32,
+    pub snap_to_grid: bool,
+    pub snap_edges: bool,
+    pub snap_on_load: bool,
+    pub pixel_zoom: bool,
+    pub limit_pan: bool,
+    pub mirror_about_origin: bool,
+    pub show_overlaps: bool,
+    pub show_ids: bool,
+    pub show_links: bool,
+    pub show_centers: bool,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            show_grid: true,
+            grid_size: 1.0,
+            grid_subdivisions: 1,
+            snap_to_grid: false,
+            snap_edges: false,
+            snap_on_load: false,
+            pixel_zoom: false,
+            limit_pan: false,
+            mirror_about_origin: false,
+            show_overlaps: false,
+            show_ids: false,
+            show_links: false,
+            show_centers: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ObjectTemplate {
+    pub scale: [f32; 2],
+    pub tint: [u8; 4],
+    pub tags: Vec<String>,
+    pub properties: BTreeMap<String, String>,
+}
+
+impl Default for ObjectTemplate {
+    fn default() -> Self {
+        Self {
+            scale: [1.0, 1.0],
+            tint: [255, 255, 255, 255],
+            tags: Vec::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            pan_speed: 10.0,
+            zoom_speed: 0.01,
+            invert_zoom: false,
+            object_template: ObjectTemplate::default(),
+            selected_color: [255, 255, 0, 255],
+            outline_color: [173, 216, 230, 255],
+            antialiasing: true,
+            staged_edits: false,
+            remember_session: true,
+            negative_scale: true,
+            show_fps: false,
+            show_hierarchy: true,
+            show_inspector: true,
+            texture_budget_mb: 512,
+            pixel_art: false,
+            smooth_minify: true,
+            auto_name: true,
+            sort_on_save: false,
+            frame_margin: 10.0,
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+impl Preferences {
+    // 줌에 비례하되 읽을 수 있는 범위로 제한
+    pub fn stroke_width(&self, zoom: f32) -> f32 {
+        (2.0 * zoom).clamp(1.0, 4.0)
+    }
+
+    pub fn texture_budget(&self) -> Option<usize> {
+        (self.texture_budget_mb > 0).then(|| self.texture_budget_mb as usize * 1024 * 1024)
+    }
+
+    // 확대(텍셀 > 화면 픽셀)와 축소 필터를 따로 정하므로 GPU가 화면 크기에 맞춰 고름
+    pub fn texture_options(&self) -> eframe::egui::TextureOptions {
+        use eframe::egui::{TextureFilter, TextureOptions};
+
+        let filter = |smooth: bool| if smooth { TextureFilter::Linear } else { TextureFilter::Nearest };
+        TextureOptions {
+            magnification: filter(!self.pixel_art),
+            minification: filter(self.smooth_minify),
+            ..TextureOptions::LINEAR
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut eframe::egui::Ui) {
+        use eframe::egui;
+
+        egui::Grid::new("preferences").num_columns(2).show(ui, |ui| {
+            ui.label("Pan speed:");
+            ui.add(egui::DragValue::new(&mut self.pan_speed).speed(0.5).clamp_range(1.0..=100.0));
+            ui.end_row();
+
+            ui.label("Zoom speed:");
+            ui.add(egui::DragValue::new(&mut self.zoom_speed).speed(0.001).clamp_range(0.001..=0.1));
+            ui.end_row();
+
+            ui.label("Invert scroll zoom:");
+            ui.checkbox(&mut self.invert_zoom, "");
+            ui.end_row();
+
+            ui.label("Selection color:");
+            ui.color_edit_button_srgba_unmultiplied(&mut self.selected_color);
+            ui.end_row();
+
+            ui.label("Outline color:");
+            ui.color_edit_button_srgba_unmultiplied(&mut self.outline_color);
+            ui.end_row();
+
+            // 끄면 외곽선 같은 도형 가장자리를 부드럽게 처리하지 않음
+            ui.label("Antialiasing:");
+            ui.checkbox(&mut self.antialiasing, "Smooth shape edges");
+            ui.end_row();
+
+            // 켜면 인스펙터 값은 Apply나 Enter를 눌러야 반영됨
+            ui.label("Inspector edits:");
+            ui.checkbox(&mut self.staged_edits, "Apply on Enter");
+            ui.end_row();
+
+            // 선택, 접힌 계층, 카메라를 씬 파일에 함께 저장
+            ui.label("Scene session:");
+            ui.checkbox(&mut self.remember_session, "Remember selection and view");
+            ui.end_row();
+
+            ui.label("Negative scale:");
+            ui.checkbox(&mut self.negative_scale, "Allow (flips the sprite)");
+            ui.end_row();
+
+            // 직접 바꾼 이름은 건드리지 않음
+            ui.label("Image naming:");
+            ui.checkbox(&mut self.auto_name, "Name unnamed objects after their image");
+            ui.end_row();
+
+            // 순서를 바꿔도 파일에서는 오브젝트가 움직이지 않아 버전 관리 diff가 작아짐
+            ui.label("Scene files:");
+            ui.checkbox(&mut self.sort_on_save, "Save objects sorted by id");
+            ui.end_row();
+
+            ui.label("Framing margin:");
+            ui.add(egui::DragValue::new(&mut self.frame_margin).speed(0.5).clamp_range(0.0..=40.0).suffix("%"))
+                .on_hover_text("Space left around objects by Frame selected and Frame all");
+            ui.end_row();
+
+            ui.label("Frame stats:");
+            ui.checkbox(&mut self.show_fps, "Show FPS in the scene view");
+            ui.end_row();
+
+            // 픽셀 아트도 멀리서 볼 때는 부드럽게 줄여서 반짝임을 줄임
+            ui.label("Texture filtering:");
+            ui.vertical(|ui| {
+                ui.checkbox(&mut self.pixel_art, "Sharp pixels when zoomed in");
+                ui.checkbox(&mut self.smooth_minify, "Smooth when zoomed out");
+            });
+            ui.end_row();
+
+            // 넘치면 가장 오래 안 그린 텍스처부터 내리고, 혼자 넘치는 이미지는 줄여서 올림
+            ui.label("Texture budget:");
+            ui.add(egui::DragValue::new(&mut self.texture_budget_mb).speed(8.0).clamp_range(0..=16384).suffix(" MB"))
+                .on_hover_text("0 = unlimited");
+            ui.end_row();
+        });
+
+        ui.collapsing("Default Object", |ui| {
+            let template = &mut self.object_template;
+            ui.horizontal(|ui| {
+                ui.label("Scale:");
+                ui.add(egui::DragValue::new(&mut template.scale[0]));
+                ui.add(egui::DragValue::new(&mut template.scale[1]));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Tint:");
+                ui.color_edit_button_srgba_unmultiplied(&mut template.tint);
+            });
+            ui.label("Tags:");
+            widgets::tags_editor(ui, "template_tags", &mut template.tags);
+            ui.label("Properties:");
+            widgets::properties_editor(ui, "template_properties", &mut template.properties);
+        });
+
+        ui.collapsing("Keyboard Shortcuts", |ui| self.keymap.ui(ui));
+
+        if ui.button("Reset to Defaults").clicked() {
+            *self = Preferences::default();
+        }
+    }
+}
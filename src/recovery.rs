@@ -0,0 +1,33 @@
+use std::fs;
+use std::sync::Mutex;
+
+pub const RECOVERY_PATH: &str = "crash_recovery.ron";
+
+// 패닉 훅에서 읽을 수 있도록 마지막 씬을 직렬화해 둠
+static SNAPSHOT: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Ok(snapshot) = SNAPSHOT.try_lock() {
+            if let Some(ron) = snapshot.as_ref() {
+                let _ = fs::write(RECOVERY_PATH, ron);
+            }
+        }
+        default_hook(info);
+    }));
+}
+
+pub fn update_snapshot(ron: String) {
+    if let Ok(mut snapshot) = SNAPSHOT.lock() {
+        *snapshot = Some(ron);
+    }
+}
+
+pub fn has_recovery() -> bool {
+    std::path::Path::new(RECOVERY_PATH).exists()
+}
+
+pub fn discard() {
+    let _ = fs::remove_file(RECOVERY_PATH);
+}
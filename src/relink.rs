@@ -0,0 +1,38 @@
+use eframe::egui;
+
+#[derive(Default)]
+pub struct Relink {
+    pub open: bool,
+    pub id: usize,
+    pub from: String,
+    pub path: String,
+    pub all: bool,
+}
+
+impl Relink {
+    pub fn start(&mut self, id: usize, from: &str) {
+        self.open = true;
+        self.id = id;
+        self.from = from.to_string();
+        self.path = from.to_string();
+        self.all = true;
+    }
+
+    // shared: 같은 깨진 경로를 쓰는 다른 오브젝트 수. Relink 버튼이 눌리면 true
+    pub fn ui(&mut self, ui: &mut egui::Ui, shared: usize) -> bool {
+        egui::Grid::new("relink").num_columns(2).show(ui, |ui| {
+            ui.label("Missing:");
+            ui.label(&self.from);
+            ui.end_row();
+
+            ui.label("New path:");
+            ui.text_edit_singleline(&mut self.path);
+            ui.end_row();
+        });
+        if shared > 0 {
+            ui.checkbox(&mut self.all, format!("Also remap {} other object(s) using this path", shared));
+        }
+        let ready = !self.path.trim().is_empty() && self.path.trim() != self.from;
+        ui.add_enabled(ready, egui::Button::new("Relink")).clicked()
+    }
+}
@@ -1,18 +1,267 @@
+use ron::error::{Position, SpannedError};
 use ron::{de::from_str, ser::to_string};
+use serde::de::{Deserializer, Error, SeqAccess, Visitor};
+use std::cell::RefCell;
 use std::fs;
-use crate::editor::GameObject;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use crate::model::{GameObject, Scene};
 
-pub fn save_scene(objects: &Vec<GameObject>, path: &str) {
-    if let Ok(ron_string) = to_string(objects) {
-        let _ = fs::write(path, ron_string);
+// 씬 파일 형식 버전. 이보다 새 파일은 모르는 필드가 있을 수 있음
+pub const SCENE_VERSION: u32 = 1;
+
+// 실패하면 사람이 읽을 수 있는 오류 메시지
+pub fn save_scene(scene: &Scene, path: &str) -> Result<(), String> {
+    write_ron(scene, path)
+}
+
+fn write_ron<T: serde::Serialize + ?Sized>(value: &T, path: &str) -> Result<(), String> {
+    let ron_string = to_string(value).map_err(|e| format!("Could not serialize {}: {}", path, e))?;
+    fs::write(path, ron_string).map_err(|e| format!("Could not write {}: {}", path, e))
+}
+
+// 없는 파일은 빈 씬, 읽을 수 없거나 형식이 틀리면 사람이 읽을 수 있는 오류 메시지
+pub fn load_scene(path: &str) -> Result<Scene, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse_scene(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Scene::default()),
+        Err(e) => Err(format!("Could not read {}: {}", path, e)),
+    }
+}
+
+// parse_scene_with_progress가 읽는 동안만: 읽은 오브젝트 수와 취소 여부
+thread_local! {
+    static PROGRESS: RefCell<Option<(Arc<AtomicUsize>, Arc<AtomicBool>)>> = const { RefCell::new(None) };
+}
+
+// Scene.objects를 하나씩 읽으며 진행 상황을 알리고, 취소되면 남은 부분은 읽지 않음
+pub(crate) fn deserialize_objects<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<GameObject>, D::Error> {
+    struct Objects;
+
+    impl<'de> Visitor<'de> for Objects {
+        type Value = Vec<GameObject>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a list of objects")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let progress = PROGRESS.with(|p| p.borrow().clone());
+            let mut objects = Vec::new();
+            while let Some(obj) = seq.next_element()? {
+                objects.push(obj);
+                if let Some((parsed, cancel)) = &progress {
+                    parsed.fetch_add(1, Ordering::Relaxed);
+                    if cancel.load(Ordering::Relaxed) {
+                        return Err(A::Error::custom("cancelled"));
+                    }
+                }
+            }
+            Ok(objects)
+        }
+    }
+
+    deserializer.deserialize_seq(Objects)
+}
+
+// 백그라운드에서 큰 씬을 읽을 때: 오브젝트를 하나 읽을 때마다 parsed를 늘리고 cancel을 확인
+pub fn parse_scene_with_progress(content: &str, parsed: &Arc<AtomicUsize>, cancel: &Arc<AtomicBool>) -> Result<Scene, String> {
+    PROGRESS.with(|p| *p.borrow_mut() = Some((parsed.clone(), cancel.clone())));
+    let scene = parse_scene(content);
+    PROGRESS.with(|p| *p.borrow_mut() = None);
+    scene
+}
+
+// 파일 내용으로 어림한 오브젝트 수 (오브젝트마다 반드시 있는 필드를 셈)
+pub fn estimate_objects(content: &str) -> usize {
+    content.matches("image_path:").count()
+}
+
+pub fn parse_scene(content: &str) -> Result<Scene, String> {
+    let error = match from_str::<Scene>(content) {
+        Ok(mut scene) => {
+            scene.apply_draw_order();
+            scene.drop_empty_image_paths();
+            return Ok(scene);
+        }
+        Err(e) => e,
+    };
+    // 예전 형식: 오브젝트 목록만 저장된 파일
+    match parse_legacy(content) {
+        Ok(objects) => {
+            let mut scene = Scene { objects, ..Scene::default() };
+            scene.drop_empty_image_paths();
+            Ok(scene)
+        }
+        // 목록으로 시작하는 파일이면 예전 형식의 오류가 더 정확함
+        Err(legacy) if content.trim_start().starts_with('[') => Err(describe_error(content, &legacy)),
+        Err(_) => Err(describe_error(content, &error)),
+    }
+}
+
+fn parse_legacy(content: &str) -> Result<Vec<GameObject>, SpannedError> {
+    let mut deserializer = ron::Deserializer::from_str(content)?;
+    let objects = deserialize_objects(&mut deserializer).map_err(|e| deserializer.span_error(e))?;
+    deserializer.end().map_err(|e| deserializer.span_error(e))?;
+    Ok(objects)
+}
+
+// "줄 L, 칸 C: 메시지"와 해당 줄, 위치를 가리키는 ^
+fn describe_error(content: &str, error: &SpannedError) -> String {
+    let Position { line, col } = error.position;
+    let snippet = content.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    // 아주 긴 한 줄 파일은 오류 위치 주변만 보여줌
+    let start = col.saturating_sub(40);
+    let excerpt: String = snippet.chars().skip(start).take(80).collect();
+    let marker = " ".repeat(col.saturating_sub(1) - start);
+    format!("Line {}, column {}: {}\n\n{}\n{}^", line, col, error.code, excerpt, marker)
+}
+
+// 씬이 쓰는 에셋 경로를 중복 없이 정렬해서, 디스크에 있는지와 함께
+pub fn asset_manifest(scene: &Scene) -> Vec<(String, bool)> {
+    let paths: std::collections::BTreeSet<&str> = scene.objects.iter()
+        .filter_map(|o| o.image_path.as_deref())
+        .filter(|p| !p.is_empty())
+        .collect();
+    paths.into_iter().map(|p| (p.to_string(), std::path::Path::new(p).exists())).collect()
+}
+
+// 한 줄에 하나씩 "ok<TAB>경로" 또는 "missing<TAB>경로"
+pub fn save_manifest(assets: &[(String, bool)], path: &str) -> bool {
+    let lines: String = assets.iter()
+        .map(|(asset, exists)| format!("{}\t{}\n", if *exists { "ok" } else { "missing" }, asset))
+        .collect();
+    fs::write(path, lines).is_ok()
+}
+
+// CSV 한 줄에서 읽은 배치 정보. 비어 있는 선택 열은 None
+pub struct CsvRow {
+    pub name: Option<String>,
+    pub position: [f32; 2],
+    pub rotation: Option<f32>,
+    pub scale: [Option<f32>; 2],
+    pub image_path: Option<String>,
+}
+
+const CSV_COLUMNS: [&str; 7] = ["name", "x", "y", "rotation", "scale_x", "scale_y", "image_path"];
+
+// name,x,y,rotation,scale_x,scale_y,image_path (회전은 씬 파일과 같은 라디안).
+// 첫 줄에 x가 숫자가 아니면 헤더로 보고 열 이름으로 순서를 정함. 잘못된 줄은 건너뛰고 줄 번호를 돌려줌
+pub fn load_csv(path: &str) -> Result<(Vec<CsvRow>, Vec<usize>), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    let mut lines = content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).peekable();
+    let mut columns: Vec<Option<usize>> = (0..CSV_COLUMNS.len()).map(Some).collect();
+    if let Some((_, first)) = lines.peek() {
+        let fields = split_csv(first);
+        if fields.get(1).is_none_or(|x| x.trim().parse::<f32>().is_err()) {
+            let names: Vec<String> = fields.iter().map(|f| f.trim().to_lowercase()).collect();
+            columns = CSV_COLUMNS.iter().map(|c| names.iter().position(|n| n == c)).collect();
+            if columns[1].is_none() || columns[2].is_none() {
+                return Err(format!("{} has no x and y columns", path));
+            }
+            lines.next();
+        }
+    }
+
+    let (mut rows, mut skipped) = (Vec::new(), Vec::new());
+    for (n, line) in lines {
+        let fields = split_csv(line);
+        let field = |column: usize| {
+            columns[column].and_then(|i| fields.get(i)).map(|f| f.trim()).filter(|f| !f.is_empty())
+        };
+        // 숫자 열이 있는데 숫자가 아니면 그 줄은 잘못된 것으로 봄
+        let number = |column: usize| field(column).map(|f| f.parse::<f32>().map_err(|_| ())).transpose();
+        let (Ok(Some(x)), Ok(Some(y)), Ok(rotation), Ok(scale_x), Ok(scale_y)) =
+            (number(1), number(2), number(3), number(4), number(5))
+        else {
+            skipped.push(n + 1);
+            continue;
+        };
+        rows.push(CsvRow {
+            name: field(0).map(str::to_string),
+            position: [x, y],
+            rotation,
+            scale: [scale_x, scale_y],
+            image_path: field(6).map(str::to_string),
+        });
+    }
+    Ok((rows, skipped))
+}
+
+// 큰따옴표로 감싼 칸 안의 쉼표와 "" 이스케이프를 처리
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
     }
+    fields
+}
+
+// 프리팹 파일: 오브젝트 목록만 저장
+pub fn save_prefab(objects: &[GameObject], path: &str) -> Result<(), String> {
+    write_ron(objects, path)
+}
+
+pub fn load_prefab(path: &str) -> Option<Vec<GameObject>> {
+    parse_prefab(&fs::read_to_string(path).ok()?)
+}
+
+pub fn parse_prefab(content: &str) -> Option<Vec<GameObject>> {
+    from_str(content).ok()
 }
 
-pub fn load_scene(path: &str) -> Vec<GameObject> {
-    if let Ok(content) = fs::read_to_string(path) {
-        if let Ok(objs) = from_str::<Vec<GameObject>>(&content) {
-            return objs;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_reports_write_errors() {
+        let path = std::env::temp_dir().join("stareditor-missing-dir").join("scene.ron");
+        let error = save_scene(&Scene::default(), path.to_str().unwrap()).unwrap_err();
+        assert!(error.starts_with("Could not write"), "{}", error);
+    }
+
+    #[test]
+    fn empty_image_path_loads_as_none() {
+        let content = r#"[(id: 1, name: "A", position: (0.0, 0.0), rotation: 0.0, scale: (1.0, 1.0), image_path: Some(""))]"#;
+        let scene = parse_scene(content).unwrap();
+        assert_eq!(scene.objects[0].image_path, None);
+        // 다시 저장해도 빈 경로가 생기지 않음
+        let saved = parse_scene(&to_string(&scene).unwrap()).unwrap();
+        assert_eq!(saved.objects[0].image_path, None);
+    }
+
+    #[test]
+    fn parse_reports_objects_and_stops_when_cancelled() {
+        let mut scene = Scene::default();
+        let object = parse_scene(r#"[(id: 0, name: "A", position: (0.0, 0.0), rotation: 0.0, scale: (1.0, 1.0), image_path: None)]"#)
+            .unwrap().objects.remove(0);
+        scene.objects = (0..5).map(|id| object.duplicate(id)).collect();
+        // 새 형식과 오브젝트 목록만 있는 예전 형식 모두
+        for content in [to_string(&scene).unwrap(), to_string(&scene.objects).unwrap()] {
+            assert_eq!(estimate_objects(&content), 5);
+
+            let (parsed, cancel) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicBool::new(false)));
+            assert_eq!(parse_scene_with_progress(&content, &parsed, &cancel).unwrap().objects.len(), 5);
+            assert_eq!(parsed.load(Ordering::Relaxed), 5);
+
+            // 첫 오브젝트를 읽고 바로 멈춤
+            let parsed = Arc::new(AtomicUsize::new(0));
+            cancel.store(true, Ordering::Relaxed);
+            assert!(parse_scene_with_progress(&content, &parsed, &cancel).is_err());
+            assert_eq!(parsed.load(Ordering::Relaxed), 1);
+            // 진행 상황 없이 읽을 때는 영향 없음
+            assert!(parse_scene(&content).is_ok());
         }
     }
-    Vec::new()
-}
\ No newline at end of file
+}
@@ -0,0 +1,75 @@
+use eframe::egui;
+
+pub struct SheetImport {
+    pub open: bool,
+    pub path: String,
+    pub tile: [u32; 2],
+    pub margin: u32,
+    pub spacing: u32,
+}
+
+impl Default for SheetImport {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: String::new(),
+            tile: [16, 16],
+            margin: 0,
+            spacing: 0,
+        }
+    }
+}
+
+impl SheetImport {
+    // 시트 크기(px)를 받아 (행, 열, uv) 목록을 반환
+    pub fn slice(&self, sheet: [usize; 2]) -> Vec<(u32, u32, [f32; 4])> {
+        let [w, h] = [sheet[0] as u32, sheet[1] as u32];
+        let [tw, th] = self.tile;
+        if tw == 0 || th == 0 {
+            return Vec::new();
+        }
+        let count = |total: u32, tile: u32| {
+            (total.saturating_sub(self.margin * 2) + self.spacing) / (tile + self.spacing)
+        };
+
+        let mut tiles = Vec::new();
+        for row in 0..count(h, th) {
+            for col in 0..count(w, tw) {
+                let x = self.margin + col * (tw + self.spacing);
+                let y = self.margin + row * (th + self.spacing);
+                tiles.push((row, col, [
+                    x as f32 / w as f32,
+                    y as f32 / h as f32,
+                    (x + tw) as f32 / w as f32,
+                    (y + th) as f32 / h as f32,
+                ]));
+            }
+        }
+        tiles
+    }
+
+    // Import 버튼이 눌리면 true
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        egui::Grid::new("sheet_import").num_columns(2).show(ui, |ui| {
+            ui.label("Image path:");
+            ui.text_edit_singleline(&mut self.path);
+            ui.end_row();
+
+            ui.label("Tile size (px):");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.tile[0]).clamp_range(1..=4096));
+                ui.add(egui::DragValue::new(&mut self.tile[1]).clamp_range(1..=4096));
+            });
+            ui.end_row();
+
+            ui.label("Margin (px):");
+            ui.add(egui::DragValue::new(&mut self.margin));
+            ui.end_row();
+
+            ui.label("Spacing (px):");
+            ui.add(egui::DragValue::new(&mut self.spacing));
+            ui.end_row();
+        });
+        ui.button("Import").clicked()
+    }
+}
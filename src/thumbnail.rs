@@ -0,0 +1,124 @@
+use image::{Rgba, RgbaImage};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::model::Scene;
+
+// 썸네일 한 변의 픽셀 수
+pub const THUMBNAIL_SIZE: u32 = 128;
+
+// 씬 파일 옆에 둘 캐시 경로: scene.ron → scene.ron.thumb.png
+pub fn thumbnail_path(scene_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.thumb.png", scene_path))
+}
+
+// 씬 파일보다 새로 만든 썸네일이 있으면 그 경로
+pub fn cached_thumbnail(scene_path: &str) -> Option<PathBuf> {
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let thumb = thumbnail_path(scene_path);
+    (modified(&thumb)? >= modified(Path::new(scene_path))?).then_some(thumb)
+}
+
+pub fn write_thumbnail(scene: &Scene, scene_path: &str) -> bool {
+    render(scene, THUMBNAIL_SIZE).save(thumbnail_path(scene_path)).is_ok()
+}
+
+// 경로마다 백그라운드 작업은 하나만. 그리는 중에 또 저장하면 마지막 씬만 남겨 두었다가 이어서 그림
+#[derive(Default)]
+pub struct ThumbnailWriter {
+    // 작업 중인 경로 → 다음에 그릴 씬
+    jobs: Arc<Mutex<HashMap<String, Option<Scene>>>>,
+}
+
+impl ThumbnailWriter {
+    pub fn write(&self, scene: Scene, scene_path: &str) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(next) = jobs.get_mut(scene_path) {
+            *next = Some(scene);
+            return;
+        }
+        jobs.insert(scene_path.to_string(), None);
+        let (jobs, path) = (self.jobs.clone(), scene_path.to_string());
+        std::thread::spawn(move || {
+            let mut scene = scene;
+            loop {
+                write_thumbnail(&scene, &path);
+                let mut jobs = jobs.lock().unwrap();
+                match jobs.get_mut(&path).and_then(Option::take) {
+                    Some(next) => scene = next,
+                    None => {
+                        jobs.remove(&path);
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+// 창 없이 씬 경계를 size x size 안에 맞춰 그림. 이미지가 없는 오브젝트는 회색 사각형
+pub fn render(scene: &Scene, size: u32) -> RgbaImage {
+    let mut out = RgbaImage::from_pixel(size, size, Rgba(scene.background_color));
+    let Some(bounds) = scene.bounds() else {
+        return out;
+    };
+    // 가장자리에 조금 여유를 두고 긴 쪽에 맞춤
+    let scale = size as f32 * 0.9 / bounds.width().max(bounds.height()).max(f32::EPSILON);
+    let to_world = |x: f32, y: f32| {
+        [
+            bounds.center().x + (x - size as f32 / 2.0) / scale,
+            bounds.center().y + (y - size as f32 / 2.0) / scale,
+        ]
+    };
+    let to_pixel = |v: f32, center: f32| (v - center) * scale + size as f32 / 2.0;
+
+    let mut images: HashMap<&str, Option<RgbaImage>> = HashMap::new();
+    let worlds = scene.world_transforms();
+    for (obj, world) in scene.objects.iter().zip(&worlds) {
+        if !scene.is_visible(obj) {
+            continue;
+        }
+        let body = world.centered(obj.pivot);
+        let aabb = body.aabb();
+        let image = obj.image_path.as_deref().and_then(|path| {
+            images.entry(path)
+                .or_insert_with(|| crate::loader::open_image(path).map(|i| i.to_rgba8()))
+                .as_ref()
+        });
+        let uv = obj.uv();
+        let x_range = to_pixel(aabb.left(), bounds.center().x).floor().max(0.0) as u32
+            ..(to_pixel(aabb.right(), bounds.center().x).ceil() as u32).min(size);
+        let y_range = to_pixel(aabb.top(), bounds.center().y).floor().max(0.0) as u32
+            ..(to_pixel(aabb.bottom(), bounds.center().y).ceil() as u32).min(size);
+        for y in y_range {
+            for x in x_range.clone() {
+                // 씬 뷰와 같은 변환의 역으로 사각형 안의 위치(0..1)를 구함
+                let [u, v] = body.pivot_at(to_world(x as f32 + 0.5, y as f32 + 0.5));
+                if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+                    continue;
+                }
+                let color = match image {
+                    Some(image) => {
+                        let tx = uv.min.x + u * uv.width();
+                        let ty = uv.min.y + v * uv.height();
+                        let px = ((tx * image.width() as f32) as u32).min(image.width() - 1);
+                        let py = ((ty * image.height() as f32) as u32).min(image.height() - 1);
+                        let Rgba(c) = *image.get_pixel(px, py);
+                        [0, 1, 2, 3].map(|k| (c[k] as u16 * obj.tint[k] as u16 / 255) as u8)
+                    }
+                    None => [128, 128, 128, 255],
+                };
+                blend(out.get_pixel_mut(x, y), color);
+            }
+        }
+    }
+    out
+}
+
+fn blend(dst: &mut Rgba<u8>, src: [u8; 4]) {
+    let alpha = src[3] as f32 / 255.0;
+    for (d, s) in dst.0.iter_mut().zip(src).take(3) {
+        *d = (s as f32 * alpha + *d as f32 * (1.0 - alpha)).round() as u8;
+    }
+    dst.0[3] = dst.0[3].max(src[3]);
+}
@@ -0,0 +1,35 @@
+use eframe::egui;
+
+const TOAST_SECONDS: f64 = 3.0;
+
+#[derive(Default)]
+pub struct Toasts {
+    items: Vec<(String, f64)>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, ctx: &egui::Context, message: impl Into<String>) {
+        let now = ctx.input(|i| i.time);
+        self.items.push((message.into(), now + TOAST_SECONDS));
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.items.retain(|(_, until)| *until > now);
+        if self.items.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                for (message, _) in &self.items {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(message);
+                    });
+                }
+            });
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+}
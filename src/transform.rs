@@ -0,0 +1,107 @@
+// 스케일 1인 오브젝트 한 변의 길이 (월드 단위).
+// 화면에서는 scale * OBJECT_SIZE * pixels_per_unit * zoom px로 그려짐
+pub const OBJECT_SIZE: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub position: [f32; 2],
+    pub rotation: f32,
+    pub scale: [f32; 2],
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            position: [0.0, 0.0],
+            rotation: 0.0,
+            scale: [1.0, 1.0],
+        }
+    }
+}
+
+fn rotate(v: [f32; 2], angle: f32) -> [f32; 2] {
+    let (sin, cos) = angle.sin_cos();
+    [v[0] * cos - v[1] * sin, v[0] * sin + v[1] * cos]
+}
+
+fn safe_div(a: f32, b: f32) -> f32 {
+    if b.abs() < f32::EPSILON { a } else { a / b }
+}
+
+impl Transform {
+    // 부모(self) 공간의 로컬 변환을 월드 변환으로
+    pub fn apply(&self, local: Transform) -> Transform {
+        let scaled = [local.position[0] * self.scale[0], local.position[1] * self.scale[1]];
+        let offset = rotate(scaled, self.rotation);
+        Transform {
+            position: [self.position[0] + offset[0], self.position[1] + offset[1]],
+            rotation: self.rotation + local.rotation,
+            scale: [self.scale[0] * local.scale[0], self.scale[1] * local.scale[1]],
+        }
+    }
+
+    // apply의 역: 월드 변환을 부모(self) 공간의 로컬 변환으로
+    pub fn localize(&self, world: Transform) -> Transform {
+        let delta = [world.position[0] - self.position[0], world.position[1] - self.position[1]];
+        let unrotated = rotate(delta, -self.rotation);
+        Transform {
+            position: [safe_div(unrotated[0], self.scale[0]), safe_div(unrotated[1], self.scale[1])],
+            rotation: world.rotation - self.rotation,
+            scale: [safe_div(world.scale[0], self.scale[0]), safe_div(world.scale[1], self.scale[1])],
+        }
+    }
+
+    // 월드 단위 크기 (뒤집힌 스케일이면 음수)
+    pub fn size(&self) -> [f32; 2] {
+        [self.scale[0] * OBJECT_SIZE, self.scale[1] * OBJECT_SIZE]
+    }
+
+    // 피벗(0..1, 0.5가 가운데) 위치에서 사각형 중심으로 옮긴 변환
+    pub fn centered(&self, pivot: [f32; 2]) -> Transform {
+        let size = self.size();
+        let offset = rotate([(0.5 - pivot[0]) * size[0], (0.5 - pivot[1]) * size[1]], self.rotation);
+        Transform {
+            position: [self.position[0] + offset[0], self.position[1] + offset[1]],
+            ..*self
+        }
+    }
+
+    // centered의 역: 중심 변환(self)에서 본 점을 피벗 좌표로
+    pub fn pivot_at(&self, point: [f32; 2]) -> [f32; 2] {
+        let delta = [point[0] - self.position[0], point[1] - self.position[1]];
+        let local = rotate(delta, -self.rotation);
+        let size = self.size();
+        [0.5 + safe_div(local[0], size[0]), 0.5 + safe_div(local[1], size[1])]
+    }
+
+    // 중심 변환(self)의 네 꼭짓점. 크기 → 회전 → 이동 순서이고, 왼쪽 위부터 시계 방향
+    // (음수 스케일이면 꼭짓점 순서가 뒤집혀 텍스처도 함께 뒤집힘)
+    pub fn corners(&self) -> [[f32; 2]; 4] {
+        let [w, h] = self.size().map(|v| v / 2.0);
+        [[-w, -h], [w, -h], [w, h], [-w, h]].map(|corner| {
+            let offset = rotate(corner, self.rotation);
+            [self.position[0] + offset[0], self.position[1] + offset[1]]
+        })
+    }
+
+    // 중심 변환(self)의 사각형 안에 점이 있는지 (corners와 같은 변환의 역)
+    pub fn contains(&self, point: [f32; 2]) -> bool {
+        let [u, v] = self.pivot_at(point);
+        (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v)
+    }
+
+    // 회전을 반영한 월드 좌표 AABB
+    pub fn aabb(&self) -> emath::Rect {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_w = self.size()[0].abs() / 2.0;
+        let half_h = self.size()[1].abs() / 2.0;
+        let extent = emath::vec2(
+            half_w * cos.abs() + half_h * sin.abs(),
+            half_w * sin.abs() + half_h * cos.abs(),
+        );
+        emath::Rect::from_center_size(
+            emath::pos2(self.position[0], self.position[1]),
+            extent * 2.0,
+        )
+    }
+}
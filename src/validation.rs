@@ -0,0 +1,160 @@
+use eframe::egui;
+use std::collections::HashSet;
+use crate::model::Scene;
+
+pub struct Validation {
+    pub open: bool,
+    pub check_stacked: bool,
+    pub tolerance: f32,
+    pub check_strays: bool,
+    // 중앙값 위치에서 이보다 멀면 길 잃은 오브젝트로 봄 (월드 단위)
+    pub stray_distance: f32,
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Self {
+            open: false,
+            check_stacked: true,
+            tolerance: 0.01,
+            check_strays: true,
+            stray_distance: 1000.0,
+        }
+    }
+}
+
+pub enum Fix {
+    Select(Vec<usize>),
+    Relink(usize),
+    Separate(Vec<usize>),
+    BringBack(Vec<usize>),
+}
+
+// 월드 위치가 tolerance 안으로 겹치는 오브젝트 묶음 (인덱스)
+pub fn find_stacked(scene: &Scene, tolerance: f32) -> Vec<Vec<usize>> {
+    let worlds = scene.world_transforms();
+    let mut taken = vec![false; worlds.len()];
+    let mut groups = Vec::new();
+    for i in 0..worlds.len() {
+        if taken[i] {
+            continue;
+        }
+        let group: Vec<usize> = (i..worlds.len())
+            .filter(|&j| {
+                let [dx, dy] = [worlds[j].position[0] - worlds[i].position[0], worlds[j].position[1] - worlds[i].position[1]];
+                !taken[j] && dx.abs() <= tolerance && dy.abs() <= tolerance
+            })
+            .collect();
+        if group.len() > 1 {
+            for &j in &group {
+                taken[j] = true;
+            }
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+// 다른 오브젝트들의 중앙값 위치에서 distance보다 멀리 떨어진 오브젝트 (인덱스).
+// 평균은 멀리 날아간 오브젝트 자체에 끌려가므로 중앙값을 씀
+pub fn find_strays(scene: &Scene, distance: f32) -> Vec<usize> {
+    let worlds = scene.world_transforms();
+    if worlds.len() < 3 {
+        return Vec::new();
+    }
+    let median = |axis: usize| {
+        let mut values: Vec<f32> = worlds.iter().map(|w| w.position[axis]).collect();
+        values.sort_by(f32::total_cmp);
+        values[values.len() / 2]
+    };
+    let center = [median(0), median(1)];
+    (0..worlds.len())
+        .filter(|&i| {
+            let [dx, dy] = [worlds[i].position[0] - center[0], worlds[i].position[1] - center[1]];
+            dx.hypot(dy) > distance
+        })
+        .collect()
+}
+
+impl Validation {
+    // 버튼을 누르면 편집기가 처리할 수정 동작을 돌려줌
+    pub fn ui(&mut self, ui: &mut egui::Ui, scene: &Scene, failed: &HashSet<String>) -> Option<Fix> {
+        let mut fix = None;
+        let mut issues = 0;
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.check_stacked, "Check stacked objects");
+            ui.add_enabled(
+                self.check_stacked,
+                egui::DragValue::new(&mut self.tolerance).speed(0.01).clamp_range(0.0..=10.0).prefix("tolerance "),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.check_strays, "Check stray objects");
+            ui.add_enabled(
+                self.check_strays,
+                egui::DragValue::new(&mut self.stray_distance).speed(10.0).clamp_range(1.0..=1_000_000.0).prefix("farther than "),
+            );
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for (i, obj) in scene.objects.iter().enumerate() {
+                let Some(path) = obj.image_path.as_ref().filter(|p| failed.contains(*p)) else {
+                    continue;
+                };
+                issues += 1;
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(255, 0, 255), "⚠");
+                    ui.label(format!("{}: missing image {}", obj.name, path));
+                    if ui.small_button("Select").clicked() {
+                        fix = Some(Fix::Select(vec![i]));
+                    }
+                    if ui.small_button("Relink...").clicked() {
+                        fix = Some(Fix::Relink(i));
+                    }
+                });
+            }
+
+            if self.check_stacked {
+                for group in find_stacked(scene, self.tolerance) {
+                    issues += 1;
+                    let names: Vec<&str> = group.iter().map(|&i| scene.objects[i].name.as_str()).collect();
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::YELLOW, "⚠");
+                        ui.label(format!("Stacked at the same position: {}", names.join(", ")));
+                        if ui.small_button("Select").clicked() {
+                            fix = Some(Fix::Select(group.clone()));
+                        }
+                        if ui.small_button("Separate").clicked() {
+                            fix = Some(Fix::Separate(group.clone()));
+                        }
+                    });
+                }
+            }
+
+            if self.check_strays {
+                let strays = find_strays(scene, self.stray_distance);
+                if !strays.is_empty() {
+                    issues += 1;
+                    let names: Vec<&str> = strays.iter().map(|&i| scene.objects[i].name.as_str()).collect();
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::YELLOW, "⚠");
+                        ui.label(format!("Far from the rest of the scene: {}", names.join(", ")));
+                        if ui.small_button("Select").clicked() {
+                            fix = Some(Fix::Select(strays.clone()));
+                        }
+                        if ui.small_button("Bring Back").on_hover_text("Move them to the middle of the view").clicked() {
+                            fix = Some(Fix::BringBack(strays.clone()));
+                        }
+                    });
+                }
+            }
+
+            if issues == 0 {
+                ui.label("No problems found.");
+            }
+        });
+        fix
+    }
+}
@@ -0,0 +1,57 @@
+use eframe::egui;
+use std::collections::BTreeMap;
+
+pub fn tags_editor(ui: &mut egui::Ui, id_source: impl std::hash::Hash, tags: &mut Vec<String>) {
+    let id = ui.make_persistent_id(id_source);
+    ui.horizontal_wrapped(|ui| {
+        let mut remove = None;
+        for (i, tag) in tags.iter().enumerate() {
+            if ui.small_button(format!("{} ✖", tag)).clicked() {
+                remove = Some(i);
+            }
+        }
+        if let Some(i) = remove {
+            tags.remove(i);
+        }
+
+        let mut draft: String = ui.data_mut(|d| d.get_temp(id).unwrap_or_default());
+        let edit = ui.add(egui::TextEdit::singleline(&mut draft).hint_text("new tag").desired_width(80.0));
+        let submit = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (submit || ui.small_button("+").clicked()) && !draft.trim().is_empty() {
+            let tag = draft.trim().to_string();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            draft.clear();
+        }
+        ui.data_mut(|d| d.insert_temp(id, draft));
+    });
+}
+
+pub fn properties_editor(ui: &mut egui::Ui, id_source: impl std::hash::Hash, properties: &mut BTreeMap<String, String>) {
+    let id = ui.make_persistent_id(id_source);
+    let mut remove = None;
+    egui::Grid::new(id.with("grid")).num_columns(3).show(ui, |ui| {
+        for (key, value) in properties.iter_mut() {
+            ui.label(key);
+            ui.add(egui::TextEdit::singleline(value).desired_width(100.0));
+            if ui.small_button("✖").clicked() {
+                remove = Some(key.clone());
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(key) = remove {
+        properties.remove(&key);
+    }
+
+    ui.horizontal(|ui| {
+        let mut draft: String = ui.data_mut(|d| d.get_temp(id).unwrap_or_default());
+        ui.add(egui::TextEdit::singleline(&mut draft).hint_text("new property").desired_width(100.0));
+        if ui.small_button("+").clicked() && !draft.trim().is_empty() {
+            properties.entry(draft.trim().to_string()).or_default();
+            draft.clear();
+        }
+        ui.data_mut(|d| d.insert_temp(id, draft));
+    });
+}